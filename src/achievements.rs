@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ending::RunStats,
+    events::{GameOver, RentPaid},
+    profile::Profile,
+    toast::{ToastKind, Toasts},
+    CurrentHealth, Player,
+};
+
+const BILLS_FOR_BUSTER: u32 = 100;
+const MONTHS_FOR_YEAR: u32 = 12;
+const GOOD_CREDIT: f32 = 800.;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Achievement {
+    FirstRent,
+    BillBuster,
+    FullYear,
+    GoodCredit,
+    NeverLate,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 5] = [
+        Achievement::FirstRent,
+        Achievement::BillBuster,
+        Achievement::FullYear,
+        Achievement::GoodCredit,
+        Achievement::NeverLate,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Achievement::FirstRent => "Responsible Adult",
+            Achievement::BillBuster => "Bill Buster",
+            Achievement::FullYear => "Made It a Year",
+            Achievement::GoodCredit => "Good Standing",
+            Achievement::NeverLate => "Never Late",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Achievement::FirstRent => "Pay rent for the first time",
+            Achievement::BillBuster => "Destroy 100 bills",
+            Achievement::FullYear => "Survive 12 months in one run",
+            Achievement::GoodCredit => "Reach a credit score of 800",
+            Achievement::NeverLate => "Win a run without missing rent",
+        }
+    }
+}
+
+/// Unlocks achievements from game events. Runs outside gameplay like `track_profile`, so the
+/// events that end a run still count.
+pub fn check_achievements(
+    mut profile: ResMut<Profile>,
+    stats: Res<RunStats>,
+    player: Query<&CurrentHealth, With<Player>>,
+    mut rent_paid: EventReader<RentPaid>,
+    mut game_over: EventReader<GameOver>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let mut earned = Vec::new();
+    if rent_paid.iter().count() > 0 {
+        earned.push(Achievement::FirstRent);
+    }
+    if profile.bills_destroyed >= BILLS_FOR_BUSTER {
+        earned.push(Achievement::BillBuster);
+    }
+    if stats.months >= MONTHS_FOR_YEAR {
+        earned.push(Achievement::FullYear);
+    }
+    if player
+        .get_single()
+        .map_or(false, |credit| credit.0 >= GOOD_CREDIT)
+    {
+        earned.push(Achievement::GoodCredit);
+    }
+    for over in game_over.iter() {
+        if over.ending.is_win() && stats.rent_missed == 0 {
+            earned.push(Achievement::NeverLate);
+        }
+    }
+
+    let mut unlocked = false;
+    for achievement in earned {
+        if profile.achievements.contains(&achievement) {
+            continue;
+        }
+        unlocked = true;
+        profile.achievements.push(achievement);
+        toasts.notify(
+            ToastKind::Success,
+            format!("ACHIEVEMENT UNLOCKED: {}", achievement.name()),
+            4.,
+        );
+    }
+    if unlocked {
+        profile.save();
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_rapier2d::control::KinematicCharacterControllerOutput;
+use serde::Deserialize;
+
+use crate::{
+    events::{CardThrown, EnemyHit, PlayerHurt},
+    CurrentHealth, Player, Velocity, CREDIT_FLOOR,
+};
+
+/// Characters with an `assets/animations/<name>.ron` file.
+pub const CHARACTERS: [&str; 2] = ["chester", "bill"];
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum AnimationClip {
+    Idle,
+    Run,
+    Jump,
+    Throw,
+    Hurt,
+    Die,
+}
+
+impl AnimationClip {
+    /// One-shot clips play to the end before the state machine picks a new clip.
+    pub fn is_one_shot(self) -> bool {
+        matches!(
+            self,
+            AnimationClip::Throw | AnimationClip::Hurt | AnimationClip::Die
+        )
+    }
+}
+
+/// Stands in for clips a character's file doesn't have or gets wrong.
+const MISSING_CLIP: ClipData = ClipData {
+    first: 0,
+    last: 0,
+    fps: 1.,
+};
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct ClipData {
+    pub first: usize,
+    pub last: usize,
+    pub fps: f32,
+}
+
+impl ClipData {
+    /// Whether the clip can be played from a sheet with `frames` frames.
+    fn valid(&self, frames: usize) -> bool {
+        self.fps > 0. && self.fps.is_finite() && self.first <= self.last && self.last < frames
+    }
+
+    pub fn duration(&self) -> f32 {
+        (self.last - self.first + 1) as f32 / self.fps
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CharacterAnimationData {
+    pub sheet: String,
+    pub tile_size: (f32, f32),
+    pub columns: usize,
+    pub rows: usize,
+    pub clips: HashMap<AnimationClip, ClipData>,
+}
+
+pub struct CharacterAnimations {
+    pub atlas: Handle<TextureAtlas>,
+    pub clips: HashMap<AnimationClip, ClipData>,
+}
+
+#[derive(Resource, Default)]
+pub struct AnimationLibrary(pub HashMap<&'static str, CharacterAnimations>);
+
+impl AnimationLibrary {
+    /// Characters whose animations failed to load get an empty handle and draw nothing.
+    pub fn atlas(&self, character: &str) -> Handle<TextureAtlas> {
+        self.0
+            .get(character)
+            .map(|c| c.atlas.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn clip(&self, character: &str, clip: AnimationClip) -> ClipData {
+        self.0
+            .get(character)
+            .and_then(|c| c.clips.get(&clip).copied())
+            .unwrap_or(MISSING_CLIP)
+    }
+}
+
+#[derive(Component)]
+pub struct Animator {
+    pub character: &'static str,
+    pub clip: AnimationClip,
+    pub frame: usize,
+    pub timer: Timer,
+    pub finished: bool,
+}
+
+impl Animator {
+    pub fn new(character: &'static str, clip: AnimationClip) -> Self {
+        Self {
+            character,
+            clip,
+            frame: 0,
+            timer: Timer::from_seconds(0., TimerMode::Repeating),
+            finished: false,
+        }
+    }
+
+    /// Switches clip, restarting it if `restart` is set or the clip changed.
+    pub fn play(&mut self, clip: AnimationClip, restart: bool) {
+        if restart || self.clip != clip {
+            self.clip = clip;
+            self.frame = 0;
+            self.timer.reset();
+            self.finished = false;
+        }
+    }
+
+    fn busy(&self) -> bool {
+        self.clip.is_one_shot() && !self.finished
+    }
+}
+
+/// Enemies that have been killed and are playing their death clip.
+#[derive(Component)]
+pub struct Dying;
+
+pub fn load_animations(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let mut library = AnimationLibrary::default();
+    for character in CHARACTERS {
+        let file = format!("assets/animations/{character}.ron");
+        let data = match std::fs::read_to_string(&file) {
+            Ok(data) => data,
+            Err(e) => {
+                println!("failed to read animations {file}: {e}");
+                continue;
+            }
+        };
+        let data: CharacterAnimationData = match ron::from_str(&data) {
+            Ok(data) => data,
+            Err(e) => {
+                println!("failed to parse animations {file}: {e}");
+                continue;
+            }
+        };
+
+        let frames = data.columns * data.rows;
+        if frames == 0 {
+            println!("animations {file} have an empty sheet");
+            continue;
+        }
+        let mut clips = data.clips;
+        for (clip, clip_data) in clips.iter_mut() {
+            if !clip_data.valid(frames) {
+                println!("bad {clip:?} clip in {file}, using the first frame");
+                *clip_data = MISSING_CLIP;
+            }
+        }
+
+        let atlas = TextureAtlas::from_grid(
+            asset_server.load(data.sheet.as_str()),
+            Vec2::new(data.tile_size.0, data.tile_size.1),
+            data.columns,
+            data.rows,
+            None,
+            None,
+        );
+        library.0.insert(
+            character,
+            CharacterAnimations {
+                atlas: atlases.add(atlas),
+                clips,
+            },
+        );
+    }
+    commands.insert_resource(library);
+}
+
+pub fn player_animation_state(
+    mut player: Query<
+        (
+            &mut Animator,
+            &Velocity,
+            &CurrentHealth,
+            Option<&KinematicCharacterControllerOutput>,
+        ),
+        With<Player>,
+    >,
+    mut hurt_events: EventReader<PlayerHurt>,
+    mut throw_events: EventReader<CardThrown>,
+) {
+    let Ok((mut animator, vel, curr_hp, output)) = player.get_single_mut() else {
+        return;
+    };
+    let hurt = hurt_events.iter().count() > 0;
+    let thrown = throw_events.iter().count() > 0;
+
+    if curr_hp.0 <= CREDIT_FLOOR {
+        animator.play(AnimationClip::Die, false);
+    } else if hurt {
+        animator.play(AnimationClip::Hurt, true);
+    } else if thrown && animator.clip != AnimationClip::Hurt {
+        animator.play(AnimationClip::Throw, true);
+    } else if !animator.busy() {
+        let grounded = output.map_or(true, |o| o.grounded);
+        let clip = if !grounded {
+            AnimationClip::Jump
+        } else if vel.0.x.abs() > 10. {
+            AnimationClip::Run
+        } else {
+            AnimationClip::Idle
+        };
+        animator.play(clip, false);
+    }
+}
+
+pub fn enemy_animation_state(
+    mut enemies: Query<(&mut Animator, Option<&Dying>), Without<Player>>,
+    mut hit_events: EventReader<EnemyHit>,
+) {
+    for hit in hit_events.iter() {
+        if let Ok((mut animator, None)) = enemies.get_mut(hit.enemy) {
+            animator.play(AnimationClip::Hurt, true);
+        }
+    }
+    for (mut animator, dying) in enemies.iter_mut() {
+        if dying.is_some() {
+            animator.play(AnimationClip::Die, false);
+        } else if !animator.busy() {
+            // Bills never stand still
+            animator.play(AnimationClip::Run, false);
+        }
+    }
+}
+
+pub fn animate_sprites(
+    time: Res<Time>,
+    library: Res<AnimationLibrary>,
+    mut query: Query<(&mut Animator, &mut TextureAtlasSprite)>,
+) {
+    for (mut animator, mut sprite) in query.iter_mut() {
+        let clip = library.clip(animator.character, animator.clip);
+        animator
+            .timer
+            .set_duration(std::time::Duration::from_secs_f32(1. / clip.fps));
+        animator.timer.tick(time.delta());
+
+        let frames = clip.last - clip.first + 1;
+        for _ in 0..animator.timer.times_finished_this_tick() {
+            if animator.frame + 1 < frames {
+                animator.frame += 1;
+            } else if animator.clip.is_one_shot() {
+                animator.finished = true;
+            } else {
+                animator.frame = 0;
+            }
+        }
+        sprite.index = clip.first + animator.frame.min(frames - 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip(first: usize, last: usize, fps: f32) -> ClipData {
+        ClipData { first, last, fps }
+    }
+
+    #[test]
+    fn clips_are_checked_against_the_sheet() {
+        assert!(clip(0, 3, 6.).valid(24));
+        assert!(clip(23, 23, 1.).valid(24));
+        assert!(MISSING_CLIP.valid(1));
+
+        assert!(!clip(0, 3, 0.).valid(24));
+        assert!(!clip(0, 3, -6.).valid(24));
+        assert!(!clip(0, 3, f32::NAN).valid(24));
+        assert!(!clip(4, 3, 6.).valid(24));
+        assert!(!clip(20, 24, 6.).valid(24));
+    }
+}
@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+
+use crate::{
+    calendar::Calendar,
+    events::{
+        BillMissed, CardThrown, EnemyHit, EnemyKilled, GameOver, PlayerHurt, RentMissed, RentPaid,
+    },
+};
+
+/// Days before rent is due when the month-end track takes over.
+const MONTH_END_DAYS: f32 = 5.;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Sfx {
+    CardThrow,
+    EnemyHit,
+    EnemyKilled,
+    PlayerHurt,
+    RentPaid,
+    RentMissed,
+    BillMissed,
+    GameOver,
+    Victory,
+}
+
+impl Sfx {
+    pub fn path(self) -> &'static str {
+        match self {
+            Sfx::CardThrow => "audio/sfx/card_throw.wav",
+            Sfx::EnemyHit => "audio/sfx/enemy_hit.wav",
+            Sfx::EnemyKilled => "audio/sfx/enemy_killed.wav",
+            Sfx::PlayerHurt => "audio/sfx/player_hurt.wav",
+            Sfx::RentPaid => "audio/sfx/rent_paid.wav",
+            Sfx::RentMissed => "audio/sfx/rent_missed.wav",
+            Sfx::BillMissed => "audio/sfx/bill_missed.wav",
+            Sfx::GameOver => "audio/sfx/game_over.wav",
+            Sfx::Victory => "audio/sfx/victory.wav",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Music {
+    Main,
+    MonthEnd,
+}
+
+impl Music {
+    pub fn path(self) -> &'static str {
+        match self {
+            Music::Main => "audio/music/main.wav",
+            Music::MonthEnd => "audio/music/month_end.wav",
+        }
+    }
+}
+
+/// Picks the track for how many days are left until rent is due.
+pub fn music_for(days_left: f32) -> Music {
+    if days_left <= MONTH_END_DAYS {
+        Music::MonthEnd
+    } else {
+        Music::Main
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SoundCategory {
+    Music,
+    Sfx,
+}
+
+#[derive(Resource)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 0.8,
+            music: 0.5,
+            sfx: 0.8,
+            muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn volume(&self, category: SoundCategory) -> f32 {
+        if self.muted {
+            return 0.;
+        }
+        self.master
+            * match category {
+                SoundCategory::Music => self.music,
+                SoundCategory::Sfx => self.sfx,
+            }
+    }
+}
+
+/// Sounds requested this frame. Filled from game events by `queue_sfx` and drained by
+/// `play_sfx`, so which events make which sounds can be checked without an audio device.
+#[derive(Resource, Default)]
+pub struct SfxQueue(pub Vec<Sfx>);
+
+#[derive(Resource, Default)]
+pub struct CurrentMusic {
+    pub track: Option<Music>,
+    pub sink: Handle<AudioSink>,
+}
+
+pub fn queue_sfx(
+    mut queue: ResMut<SfxQueue>,
+    mut thrown: EventReader<CardThrown>,
+    mut hits: EventReader<EnemyHit>,
+    mut kills: EventReader<EnemyKilled>,
+    mut hurts: EventReader<PlayerHurt>,
+    mut rent_paid: EventReader<RentPaid>,
+    mut rent_missed: EventReader<RentMissed>,
+    mut bills_missed: EventReader<BillMissed>,
+    mut game_over: EventReader<GameOver>,
+) {
+    queue.0.extend(thrown.iter().map(|_| Sfx::CardThrow));
+    queue.0.extend(hits.iter().map(|_| Sfx::EnemyHit));
+    queue.0.extend(kills.iter().map(|_| Sfx::EnemyKilled));
+    queue.0.extend(hurts.iter().map(|_| Sfx::PlayerHurt));
+    queue.0.extend(rent_paid.iter().map(|_| Sfx::RentPaid));
+    queue.0.extend(rent_missed.iter().map(|_| Sfx::RentMissed));
+    queue.0.extend(bills_missed.iter().map(|_| Sfx::BillMissed));
+    queue.0.extend(game_over.iter().map(|e| {
+        if e.ending.is_win() {
+            Sfx::Victory
+        } else {
+            Sfx::GameOver
+        }
+    }));
+}
+
+pub fn play_sfx(
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    settings: Res<AudioSettings>,
+    mut queue: ResMut<SfxQueue>,
+) {
+    let volume = settings.volume(SoundCategory::Sfx);
+    for sfx in queue.0.drain(..) {
+        if volume > 0. {
+            audio.play_with_settings(
+                asset_server.load(sfx.path()),
+                PlaybackSettings::ONCE.with_volume(volume),
+            );
+        }
+    }
+}
+
+pub fn update_music(
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    sinks: Res<Assets<AudioSink>>,
+    settings: Res<AudioSettings>,
+    calendar: Res<Calendar>,
+    mut current: ResMut<CurrentMusic>,
+) {
+    let volume = settings.volume(SoundCategory::Music);
+    let track = music_for(calendar.days_left());
+
+    if current.track != Some(track) {
+        if let Some(sink) = sinks.get(&current.sink) {
+            sink.stop();
+        }
+        let sink = audio.play_with_settings(
+            asset_server.load(track.path()),
+            PlaybackSettings::LOOP.with_volume(volume),
+        );
+        current.sink = sinks.get_handle(sink);
+        current.track = Some(track);
+    } else if settings.is_changed() {
+        if let Some(sink) = sinks.get(&current.sink) {
+            sink.set_volume(volume);
+        }
+    }
+}
+
+pub fn toggle_mute(key_input: Res<Input<KeyCode>>, mut settings: ResMut<AudioSettings>) {
+    if key_input.just_pressed(KeyCode::M) {
+        settings.muted = !settings.muted;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ending::Ending;
+
+    fn sfx_app() -> App {
+        let mut app = App::new();
+        app.add_event::<CardThrown>()
+            .add_event::<EnemyHit>()
+            .add_event::<EnemyKilled>()
+            .add_event::<PlayerHurt>()
+            .add_event::<RentPaid>()
+            .add_event::<RentMissed>()
+            .add_event::<BillMissed>()
+            .add_event::<GameOver>()
+            .init_resource::<SfxQueue>()
+            .add_system(queue_sfx);
+        app
+    }
+
+    #[test]
+    fn events_queue_their_sounds() {
+        let mut app = sfx_app();
+        app.world.send_event(PlayerHurt { damage: 1. });
+        app.world.send_event(RentPaid);
+        app.world.send_event(GameOver {
+            ending: Ending::Bankrupt,
+        });
+        app.update();
+
+        assert_eq!(
+            app.world.resource::<SfxQueue>().0,
+            vec![Sfx::PlayerHurt, Sfx::RentPaid, Sfx::GameOver]
+        );
+    }
+
+    #[test]
+    fn missed_bills_and_wins_have_their_own_sounds() {
+        let mut app = sfx_app();
+        app.world.send_event(BillMissed { credit_penalty: 5. });
+        app.world.send_event(GameOver {
+            ending: Ending::Homeowner,
+        });
+        app.update();
+
+        assert_eq!(
+            app.world.resource::<SfxQueue>().0,
+            vec![Sfx::BillMissed, Sfx::Victory]
+        );
+    }
+
+    #[test]
+    fn month_end_music_starts_five_days_out() {
+        assert_eq!(music_for(20.), Music::Main);
+        assert_eq!(music_for(5.5), Music::Main);
+        assert_eq!(music_for(5.), Music::MonthEnd);
+        assert_eq!(music_for(0.), Music::MonthEnd);
+    }
+
+    #[test]
+    fn volume_scales_by_master_and_mutes() {
+        let mut settings = AudioSettings {
+            master: 0.5,
+            music: 0.4,
+            sfx: 1.,
+            muted: false,
+        };
+        assert_eq!(settings.volume(SoundCategory::Music), 0.2);
+        assert_eq!(settings.volume(SoundCategory::Sfx), 0.5);
+
+        settings.muted = true;
+        assert_eq!(settings.volume(SoundCategory::Music), 0.);
+        assert_eq!(settings.volume(SoundCategory::Sfx), 0.);
+    }
+}
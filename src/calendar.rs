@@ -0,0 +1,269 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::load_config,
+    events::{DayStarted, MonthEnded, WeekStarted},
+};
+
+const CALENDAR_CONFIG: &str = "config/calendar.ron";
+/// Shortest day the calendar will run, whatever a config or save says.
+const MIN_DAY_SECS: f32 = 0.1;
+
+pub const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    pub fn next(self) -> Weekday {
+        match self {
+            Weekday::Monday => Weekday::Tuesday,
+            Weekday::Tuesday => Weekday::Wednesday,
+            Weekday::Wednesday => Weekday::Thursday,
+            Weekday::Thursday => Weekday::Friday,
+            Weekday::Friday => Weekday::Saturday,
+            Weekday::Saturday => Weekday::Sunday,
+            Weekday::Sunday => Weekday::Monday,
+        }
+    }
+
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Mon",
+            Weekday::Tuesday => "Tue",
+            Weekday::Wednesday => "Wed",
+            Weekday::Thursday => "Thu",
+            Weekday::Friday => "Fri",
+            Weekday::Saturday => "Sat",
+            Weekday::Sunday => "Sun",
+        }
+    }
+}
+
+#[derive(Resource, Deserialize)]
+#[serde(default)]
+pub struct CalendarConfig {
+    /// Real seconds per in-game day.
+    pub day_secs: f32,
+    pub start_year: u32,
+    pub start_weekday: Weekday,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            day_secs: 2.5,
+            start_year: 2024,
+            start_weekday: Weekday::Monday,
+        }
+    }
+}
+
+impl CalendarConfig {
+    pub fn load() -> CalendarConfig {
+        let config: CalendarConfig = load_config(CALENDAR_CONFIG);
+        if config.day_secs > 0. {
+            config
+        } else {
+            println!(
+                "ignoring calendar config with day_secs {}, using defaults",
+                config.day_secs
+            );
+            CalendarConfig::default()
+        }
+    }
+}
+
+/// In-game date. Runs start on January 1st and advance one day every `day_secs`.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct Calendar {
+    pub day_secs: f32,
+    /// Day of the month, starting at 1.
+    pub day: u32,
+    pub weekday: Weekday,
+    /// Month of the year, January is 0.
+    pub month: usize,
+    pub year: u32,
+    /// Real seconds into the current day.
+    pub day_elapsed: f32,
+    #[serde(default)]
+    pub days_played: u64,
+}
+
+impl Calendar {
+    pub fn new(config: &CalendarConfig) -> Self {
+        Self {
+            day_secs: config.day_secs,
+            day: 1,
+            weekday: config.start_weekday,
+            month: 0,
+            year: config.start_year,
+            day_elapsed: 0.,
+            days_played: 0,
+        }
+    }
+
+    /// `day_secs`, kept above zero so a bad value can't stall or freeze the clock.
+    fn day_length(&self) -> f32 {
+        self.day_secs.max(MIN_DAY_SECS)
+    }
+
+    /// A fresh calendar from the config on disk.
+    pub fn load() -> Self {
+        Calendar::new(&CalendarConfig::load())
+    }
+
+    pub fn month_name(&self) -> &'static str {
+        MONTH_NAMES[self.month]
+    }
+
+    pub fn days_in_month(&self) -> u32 {
+        match self.month {
+            1 if self.is_leap_year() => 29,
+            1 => 28,
+            3 | 5 | 8 | 10 => 30,
+            _ => 31,
+        }
+    }
+
+    fn is_leap_year(&self) -> bool {
+        (self.year % 4 == 0 && self.year % 100 != 0) || self.year % 400 == 0
+    }
+
+    /// Days until the month ends, counting the rest of today as a fraction.
+    pub fn days_left(&self) -> f32 {
+        (self.days_in_month() - self.day) as f32 + 1. - self.day_elapsed / self.day_length()
+    }
+
+    fn advance_day(&mut self) -> bool {
+        self.days_played += 1;
+        self.weekday = self.weekday.next();
+        if self.day < self.days_in_month() {
+            self.day += 1;
+            return false;
+        }
+        self.day = 1;
+        self.month += 1;
+        if self.month == MONTH_NAMES.len() {
+            self.month = 0;
+            self.year += 1;
+        }
+        true
+    }
+}
+
+pub fn tick_calendar(
+    time: Res<Time>,
+    mut calendar: ResMut<Calendar>,
+    mut day_started: EventWriter<DayStarted>,
+    mut week_started: EventWriter<WeekStarted>,
+    mut month_ended: EventWriter<MonthEnded>,
+) {
+    calendar.day_elapsed += time.delta_seconds();
+    let day_length = calendar.day_length();
+    while calendar.day_elapsed >= day_length {
+        calendar.day_elapsed -= day_length;
+        if calendar.advance_day() {
+            month_ended.send(MonthEnded);
+        }
+        day_started.send(DayStarted {
+            day: calendar.day,
+            weekday: calendar.weekday,
+        });
+        if calendar.weekday == Weekday::Monday {
+            week_started.send(WeekStarted);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calendar(year: u32, month: usize, day: u32, weekday: Weekday) -> Calendar {
+        Calendar {
+            day,
+            weekday,
+            month,
+            year,
+            ..Calendar::new(&CalendarConfig::default())
+        }
+    }
+
+    #[test]
+    fn month_rolls_over_after_its_last_day() {
+        let mut cal = calendar(2023, 0, 30, Weekday::Monday);
+        assert!(!cal.advance_day());
+        assert_eq!((cal.month, cal.day), (0, 31));
+
+        assert!(cal.advance_day());
+        assert_eq!((cal.month, cal.day), (1, 1));
+        assert_eq!(cal.days_played, 2);
+    }
+
+    #[test]
+    fn december_rolls_into_the_next_year() {
+        let mut cal = calendar(2023, 11, 31, Weekday::Sunday);
+        assert!(cal.advance_day());
+        assert_eq!((cal.year, cal.month, cal.day), (2024, 0, 1));
+    }
+
+    #[test]
+    fn february_has_a_leap_day() {
+        let mut leap = calendar(2024, 1, 28, Weekday::Wednesday);
+        assert!(!leap.advance_day());
+        assert_eq!(leap.day, 29);
+        assert!(leap.advance_day());
+        assert_eq!((leap.month, leap.day), (2, 1));
+
+        let mut common = calendar(2023, 1, 28, Weekday::Tuesday);
+        assert!(common.advance_day());
+        assert_eq!((common.month, common.day), (2, 1));
+
+        assert_eq!(calendar(1900, 1, 1, Weekday::Monday).days_in_month(), 28);
+        assert_eq!(calendar(2000, 1, 1, Weekday::Monday).days_in_month(), 29);
+    }
+
+    #[test]
+    fn bad_day_length_is_clamped() {
+        for day_secs in [0., -1., f32::NAN] {
+            let cal = Calendar {
+                day_secs,
+                ..calendar(2024, 0, 1, Weekday::Monday)
+            };
+            assert_eq!(cal.day_length(), MIN_DAY_SECS);
+            assert!(cal.days_left().is_finite());
+        }
+    }
+
+    #[test]
+    fn weekday_wraps_from_sunday_to_monday() {
+        let mut cal = calendar(2024, 5, 10, Weekday::Saturday);
+        cal.advance_day();
+        assert_eq!(cal.weekday, Weekday::Sunday);
+        cal.advance_day();
+        assert_eq!(cal.weekday, Weekday::Monday);
+    }
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use crate::Player;
+
+#[derive(Component)]
+pub struct MainCamera;
+
+#[derive(Component)]
+pub struct CameraFollow {
+    /// Half extents of the box around the camera center the player can move in freely.
+    pub dead_zone: Vec2,
+    /// How quickly the camera catches up, higher is snappier.
+    pub smoothing: f32,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            dead_zone: Vec2::new(100., 60.),
+            smoothing: 5.,
+        }
+    }
+}
+
+/// World-space rectangle the camera view is kept inside of.
+#[derive(Resource)]
+pub struct LevelBounds {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+pub fn camera_follow(
+    player: Query<&Transform, (With<Player>, Without<MainCamera>)>,
+    mut camera: Query<(&mut Transform, &CameraFollow, &OrthographicProjection), With<MainCamera>>,
+    bounds: Option<Res<LevelBounds>>,
+    time: Res<Time>,
+) {
+    let (Ok(player), Ok((mut cam_t, follow, projection))) =
+        (player.get_single(), camera.get_single_mut())
+    else {
+        return;
+    };
+
+    let cam = cam_t.translation.truncate();
+    let offset = player.translation.truncate() - cam;
+    let mut target = cam;
+    if offset.x.abs() > follow.dead_zone.x {
+        target.x += offset.x - follow.dead_zone.x * offset.x.signum();
+    }
+    if offset.y.abs() > follow.dead_zone.y {
+        target.y += offset.y - follow.dead_zone.y * offset.y.signum();
+    }
+
+    let t = 1. - (-follow.smoothing * time.delta_seconds()).exp();
+    let mut next = cam.lerp(target, t);
+
+    if let Some(bounds) = bounds {
+        let half_view = (projection.area.max - projection.area.min) / 2.;
+        next.x = clamp_axis(
+            next.x,
+            bounds.min.x + half_view.x,
+            bounds.max.x - half_view.x,
+        );
+        next.y = clamp_axis(
+            next.y,
+            bounds.min.y + half_view.y,
+            bounds.max.y - half_view.y,
+        );
+    }
+
+    cam_t.translation.x = next.x;
+    cam_t.translation.y = next.y;
+}
+
+/// Clamps into `min..max`, centering when the level is smaller than the view.
+fn clamp_axis(value: f32, min: f32, max: f32) -> f32 {
+    if min > max {
+        (min + max) / 2.
+    } else {
+        value.clamp(min, max)
+    }
+}
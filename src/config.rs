@@ -0,0 +1,15 @@
+use serde::de::DeserializeOwned;
+
+/// Reads a ron file under `assets/`, falling back to defaults so a missing or broken config
+/// never stops the game launching.
+pub fn load_config<T: DeserializeOwned + Default>(path: &str) -> T {
+    let file = format!("assets/{path}");
+    let Ok(data) = std::fs::read_to_string(&file) else {
+        println!("no config at {file}, using defaults");
+        return T::default();
+    };
+    ron::from_str(&data).unwrap_or_else(|e| {
+        println!("ignoring broken config {file}: {e}");
+        T::default()
+    })
+}
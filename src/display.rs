@@ -0,0 +1,101 @@
+use bevy::{
+    prelude::*,
+    render::camera::ScalingMode,
+    window::{PresentMode, PrimaryWindow, WindowMode, WindowResolution},
+};
+use serde::Deserialize;
+
+use crate::config::load_config;
+
+/// The world is always at least this many units across, whatever the window size.
+pub const VIRTUAL_WIDTH: f32 = 1280.;
+pub const VIRTUAL_HEIGHT: f32 = 720.;
+
+const WINDOW_CONFIG: &str = "config/window.ron";
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct WindowConfig {
+    pub title: String,
+    pub width: f32,
+    pub height: f32,
+    pub fullscreen: bool,
+    pub vsync: bool,
+    pub scale_factor: Option<f64>,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            title: "Credit Score".into(),
+            width: VIRTUAL_WIDTH,
+            height: VIRTUAL_HEIGHT,
+            fullscreen: false,
+            vsync: true,
+            scale_factor: None,
+        }
+    }
+}
+
+impl WindowConfig {
+    pub fn load() -> WindowConfig {
+        load_config(WINDOW_CONFIG)
+    }
+
+    pub fn window(&self) -> Window {
+        let mut resolution = WindowResolution::new(self.width, self.height);
+        resolution.set_scale_factor_override(self.scale_factor);
+        Window {
+            title: self.title.clone(),
+            resolution,
+            mode: window_mode(self.fullscreen),
+            present_mode: if self.vsync {
+                PresentMode::AutoVsync
+            } else {
+                PresentMode::AutoNoVsync
+            },
+            ..default()
+        }
+    }
+}
+
+fn window_mode(fullscreen: bool) -> WindowMode {
+    if fullscreen {
+        WindowMode::BorderlessFullscreen
+    } else {
+        WindowMode::Windowed
+    }
+}
+
+pub fn virtual_scaling() -> ScalingMode {
+    ScalingMode::AutoMin {
+        min_width: VIRTUAL_WIDTH,
+        min_height: VIRTUAL_HEIGHT,
+    }
+}
+
+/// Keeps UI the same size relative to the window as it is at the virtual resolution.
+pub fn update_ui_scale(
+    windows: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let scale = (window.width() / VIRTUAL_WIDTH).min(window.height() / VIRTUAL_HEIGHT);
+    if scale > 0. && (ui_scale.scale - scale as f64).abs() > f64::EPSILON {
+        ui_scale.scale = scale as f64;
+    }
+}
+
+pub fn toggle_fullscreen(
+    key_input: Res<Input<KeyCode>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !key_input.just_pressed(KeyCode::F11) {
+        return;
+    }
+    for mut window in windows.iter_mut() {
+        window.mode = window_mode(window.mode == WindowMode::Windowed);
+    }
+}
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::{CashCollected, EnemyKilled, GameOver, PaycheckReceived, RentMissed, RentPaid},
+    expenses::Bills,
+    CurrentHealth, GameState, MoneyText, Player, CREDIT_FLOOR,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Ending {
+    Homeowner,
+    DebtFree,
+    Survived,
+    Bankrupt,
+    Evicted,
+}
+
+impl Ending {
+    pub fn is_win(self) -> bool {
+        matches!(
+            self,
+            Ending::Homeowner | Ending::DebtFree | Ending::Survived
+        )
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Ending::Homeowner => "HOMEOWNER",
+            Ending::DebtFree => "DEBT-FREE",
+            Ending::Survived => "SURVIVED",
+            Ending::Bankrupt => "BANKRUPT",
+            Ending::Evicted => "EVICTED",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Ending::Homeowner => "Chester saved a down payment and the bank said yes.",
+            Ending::DebtFree => "A whole year of rent, paid. Chester owes nobody anything.",
+            Ending::Survived => {
+                "A year went by and Chester still has a roof. The debts came along too."
+            }
+            Ending::Bankrupt => "The bills won. Chester's credit score hit rock bottom.",
+            Ending::Evicted => "Too many missed rent checks. The landlord changed the locks.",
+        }
+    }
+}
+
+/// What it takes to end a run, checked at every month end.
+#[derive(Resource)]
+pub struct RunGoals {
+    pub months: u32,
+    pub credit: f32,
+    pub down_payment: i32,
+    /// Missed rents in a row before the landlord gives up.
+    pub evict_after_missed: u32,
+}
+
+impl Default for RunGoals {
+    fn default() -> Self {
+        Self {
+            months: 12,
+            credit: 800.,
+            down_payment: 10000,
+            evict_after_missed: 2,
+        }
+    }
+}
+
+/// Tally for the current run, shown on the ending screen.
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunStats {
+    pub months: u32,
+    pub kills: u32,
+    pub cash_earned: i32,
+    pub peak_cash: i32,
+    pub rent_paid: u32,
+    pub rent_missed: u32,
+    pub missed_in_a_row: u32,
+    pub ending: Option<Ending>,
+}
+
+pub fn track_run_stats(
+    mut stats: ResMut<RunStats>,
+    money: Query<&MoneyText>,
+    mut kills: EventReader<EnemyKilled>,
+    mut collected: EventReader<CashCollected>,
+    mut paychecks: EventReader<PaycheckReceived>,
+    mut rent_paid: EventReader<RentPaid>,
+    mut rent_missed: EventReader<RentMissed>,
+) {
+    for _ in kills.iter() {
+        stats.kills += 1;
+    }
+    for cash in collected.iter() {
+        stats.cash_earned += cash.amount;
+    }
+    for paycheck in paychecks.iter() {
+        stats.cash_earned += paycheck.amount;
+    }
+    for _ in rent_paid.iter() {
+        stats.months += 1;
+        stats.rent_paid += 1;
+        stats.missed_in_a_row = 0;
+    }
+    for _ in rent_missed.iter() {
+        stats.months += 1;
+        stats.rent_missed += 1;
+        stats.missed_in_a_row += 1;
+    }
+    if let Ok(money) = money.get_single() {
+        if money.0 > stats.peak_cash {
+            stats.peak_cash = money.0;
+        }
+    }
+}
+
+pub fn check_endings(
+    mut stats: ResMut<RunStats>,
+    goals: Res<RunGoals>,
+    bills: Res<Bills>,
+    player: Query<&CurrentHealth, With<Player>>,
+    money: Query<&MoneyText>,
+    mut rent_paid: EventReader<RentPaid>,
+    mut rent_missed: EventReader<RentMissed>,
+    mut game_over: EventWriter<GameOver>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let (Ok(credit), Ok(money)) = (player.get_single(), money.get_single()) else {
+        return;
+    };
+    let month_ended = rent_paid.iter().count() + rent_missed.iter().count() > 0;
+
+    let ending = if credit.0 <= CREDIT_FLOOR {
+        Some(Ending::Bankrupt)
+    } else if !month_ended {
+        None
+    } else if stats.missed_in_a_row >= goals.evict_after_missed {
+        Some(Ending::Evicted)
+    } else if money.0 >= goals.down_payment && credit.0 >= goals.credit {
+        Some(Ending::Homeowner)
+    } else if stats.months >= goals.months {
+        if stats.rent_missed == 0 && bills.total_owed() == 0 {
+            Some(Ending::DebtFree)
+        } else {
+            Some(Ending::Survived)
+        }
+    } else {
+        None
+    };
+
+    if let Some(ending) = ending {
+        stats.ending = Some(ending);
+        game_over.send(GameOver { ending });
+        next_state.set(GameState::GameOver);
+    }
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+use crate::{calendar::Weekday, ending::Ending};
+
+pub struct PlayerHurt {
+    pub damage: f32,
+}
+
+pub struct CardThrown;
+
+pub struct EnemyHit {
+    pub enemy: Entity,
+    pub damage: f32,
+}
+
+pub struct EnemyKilled {
+    pub position: Vec3,
+    /// Picks the drop table.
+    pub kind: &'static str,
+    pub cash_multiplier: f32,
+}
+
+/// A cash pickup reached the player's wallet.
+pub struct CashCollected {
+    pub amount: i32,
+    pub position: Vec3,
+}
+
+pub struct DayStarted {
+    pub day: u32,
+    pub weekday: Weekday,
+}
+
+/// Sent on Mondays.
+pub struct WeekStarted;
+
+/// Sent when the last day of a month is over, before the next one starts.
+pub struct MonthEnded;
+
+pub struct RentPaid;
+
+pub struct RentMissed;
+
+pub struct BillMissed {
+    pub credit_penalty: f32,
+}
+
+pub struct PaycheckReceived {
+    pub amount: i32,
+}
+
+pub struct GameOver {
+    pub ending: Ending,
+}
+
+/// Clears any run in progress and starts a fresh one.
+pub struct StartRun;
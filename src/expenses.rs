@@ -0,0 +1,340 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calendar::Calendar,
+    config::load_config,
+    events::{BillMissed, DayStarted},
+    toast::{ToastKind, Toasts},
+    ui::{overlay, spawn_button, spawn_sized_button, spawn_title, ButtonPressed, FONT},
+    CurrentHealth, MoneyText, Player,
+};
+
+const EXPENSES_CONFIG: &str = "config/expenses.ron";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Bill {
+    pub name: String,
+    pub amount: i32,
+    /// Day of the month it's due, moved to the last day in shorter months.
+    pub due_day: u32,
+    pub autopay: bool,
+    pub late_fee: i32,
+    pub credit_penalty: f32,
+    /// Days after the due date before it counts as missed.
+    pub grace_days: u32,
+    #[serde(default)]
+    pub owed: i32,
+    #[serde(default)]
+    pub days_late: u32,
+}
+
+/// What happened to a bill when a new day started.
+#[derive(PartialEq, Eq, Debug)]
+enum BillDay {
+    Paid(i32),
+    Missed,
+}
+
+impl Bill {
+    /// Moves the bill on a day: the amount goes on the balance on the due day, autopay clears it
+    /// if `wallet` covers it, and the late fee goes on once the grace days run out.
+    fn next_day(&mut self, day: u32, days_in_month: u32, wallet: &mut i32) -> Option<BillDay> {
+        // An unpaid balance keeps getting later, even on the day the next one comes due
+        if self.owed > 0 {
+            self.days_late += 1;
+        }
+        if day == self.due_day.min(days_in_month) {
+            self.owed += self.amount;
+        }
+        if self.owed == 0 {
+            return None;
+        }
+        if self.autopay && *wallet >= self.owed {
+            let amount = self.owed;
+            *wallet -= amount;
+            self.owed = 0;
+            self.days_late = 0;
+            Some(BillDay::Paid(amount))
+        } else if self.days_late > self.grace_days {
+            // Fee goes on the balance and the grace period starts over
+            self.owed += self.late_fee;
+            self.days_late = 0;
+            Some(BillDay::Missed)
+        } else {
+            None
+        }
+    }
+}
+
+/// Every recurring expense for the run, rent aside.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct Bills(pub Vec<Bill>);
+
+impl Bills {
+    /// Without a usable config the run just has no bills besides rent.
+    pub fn load() -> Bills {
+        Bills(load_config(EXPENSES_CONFIG))
+    }
+
+    pub fn total_owed(&self) -> i32 {
+        self.0.iter().map(|b| b.owed).sum()
+    }
+}
+
+pub fn process_bills(
+    calendar: Res<Calendar>,
+    mut bills: ResMut<Bills>,
+    mut days: EventReader<DayStarted>,
+    mut money: Query<&mut MoneyText>,
+    mut player: Query<&mut CurrentHealth, With<Player>>,
+    mut toasts: ResMut<Toasts>,
+    mut missed: EventWriter<BillMissed>,
+) {
+    for day in days.iter() {
+        let mut wallet = money.single_mut();
+        for bill in bills.0.iter_mut() {
+            match bill.next_day(day.day, calendar.days_in_month(), &mut wallet.0) {
+                Some(BillDay::Paid(amount)) => {
+                    toasts.notify(
+                        ToastKind::Info,
+                        format!("AUTOPAY: {} ${}", bill.name.to_uppercase(), amount),
+                        2.,
+                    );
+                }
+                Some(BillDay::Missed) => {
+                    player.single_mut().0 -= bill.credit_penalty;
+                    toasts.notify(
+                        ToastKind::Danger,
+                        format!(
+                            "MISSED {}: +${} LATE FEE",
+                            bill.name.to_uppercase(),
+                            bill.late_fee
+                        ),
+                        4.,
+                    );
+                    missed.send(BillMissed {
+                        credit_penalty: bill.credit_penalty,
+                    });
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct BillsPanel;
+
+#[derive(Component, Clone, Copy)]
+pub enum BillButton {
+    ToggleAutopay(usize),
+    Pay(usize),
+    Back,
+}
+
+#[derive(Component)]
+pub struct BillText(pub usize);
+
+pub fn open_bills(commands: &mut Commands, asset_server: &AssetServer, bills: &Bills) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    commands
+        .spawn((overlay(20), BillsPanel))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), "Bills");
+            for index in 0..bills.0.len() {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            gap: Size::width(Val::Px(10.)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn((
+                            TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 24.0,
+                                    color: Color::WHITE,
+                                },
+                            )
+                            .with_style(Style {
+                                size: Size::width(Val::Px(360.)),
+                                ..default()
+                            }),
+                            BillText(index),
+                        ));
+                        let size = Size::new(Val::Px(170.), Val::Px(40.));
+                        spawn_sized_button(
+                            row,
+                            font.clone(),
+                            "",
+                            size,
+                            BillButton::ToggleAutopay(index),
+                        );
+                        spawn_sized_button(row, font.clone(), "", size, BillButton::Pay(index));
+                    });
+            }
+            spawn_button(parent, font, "Back", BillButton::Back);
+        });
+}
+
+pub fn close_bills(commands: &mut Commands, panels: &Query<Entity, With<BillsPanel>>) {
+    for panel in panels.iter() {
+        commands.entity(panel).despawn_recursive();
+    }
+}
+
+pub fn bills_buttons(
+    mut pressed: EventReader<ButtonPressed>,
+    buttons: Query<&BillButton>,
+    panels: Query<Entity, With<BillsPanel>>,
+    mut bills: ResMut<Bills>,
+    mut money: Query<&mut MoneyText>,
+    mut commands: Commands,
+) {
+    for button in pressed.iter().filter_map(|p| buttons.get(p.0).ok()) {
+        match *button {
+            BillButton::ToggleAutopay(index) => bills.0[index].autopay = !bills.0[index].autopay,
+            BillButton::Pay(index) => {
+                let bill = &mut bills.0[index];
+                let mut wallet = money.single_mut();
+                if bill.owed > 0 && wallet.0 >= bill.owed {
+                    wallet.0 -= bill.owed;
+                    bill.owed = 0;
+                    bill.days_late = 0;
+                }
+            }
+            BillButton::Back => close_bills(&mut commands, &panels),
+        }
+    }
+}
+
+pub fn update_bills_panel(
+    bills: Res<Bills>,
+    added: Query<(), Added<BillsPanel>>,
+    mut bill_texts: Query<(&mut Text, &BillText)>,
+    buttons: Query<(&BillButton, &Children)>,
+    mut texts: Query<&mut Text, Without<BillText>>,
+) {
+    if !bills.is_changed() && added.is_empty() {
+        return;
+    }
+    for (mut text, index) in bill_texts.iter_mut() {
+        let bill = &bills.0[index.0];
+        text.sections[0].value = if bill.owed > 0 {
+            format!("{}: ${} OWED", bill.name, bill.owed)
+        } else {
+            format!(
+                "{}: ${} on the {}",
+                bill.name,
+                bill.amount,
+                ordinal(bill.due_day)
+            )
+        };
+        text.sections[0].style.color = if bill.owed > 0 {
+            Color::ORANGE_RED
+        } else {
+            Color::WHITE
+        };
+    }
+    for (button, children) in buttons.iter() {
+        let label = match *button {
+            BillButton::ToggleAutopay(index) if bills.0[index].autopay => "Autopay: On".into(),
+            BillButton::ToggleAutopay(_) => "Autopay: Off".into(),
+            BillButton::Pay(index) if bills.0[index].owed > 0 => {
+                format!("Pay ${}", bills.0[index].owed)
+            }
+            BillButton::Pay(_) => "Nothing due".into(),
+            BillButton::Back => continue,
+        };
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = label.clone();
+            }
+        }
+    }
+}
+
+fn ordinal(day: u32) -> String {
+    let suffix = match (day % 10, day % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{day}{suffix}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bill(autopay: bool, grace_days: u32) -> Bill {
+        Bill {
+            name: "Phone".to_string(),
+            amount: 60,
+            due_day: 12,
+            autopay,
+            late_fee: 15,
+            credit_penalty: 5.,
+            grace_days,
+            owed: 0,
+            days_late: 0,
+        }
+    }
+
+    #[test]
+    fn autopay_clears_the_bill_on_its_due_day() {
+        let mut bill = bill(true, 3);
+        let mut wallet = 100;
+        assert_eq!(bill.next_day(11, 31, &mut wallet), None);
+        assert_eq!(bill.next_day(12, 31, &mut wallet), Some(BillDay::Paid(60)));
+        assert_eq!((wallet, bill.owed), (40, 0));
+    }
+
+    #[test]
+    fn late_fee_lands_after_the_grace_days() {
+        let mut bill = bill(false, 2);
+        let mut wallet = 1000;
+        assert_eq!(bill.next_day(12, 31, &mut wallet), None);
+        assert_eq!(bill.next_day(13, 31, &mut wallet), None);
+        assert_eq!(bill.next_day(14, 31, &mut wallet), None);
+        assert_eq!(bill.next_day(15, 31, &mut wallet), Some(BillDay::Missed));
+        assert_eq!((bill.owed, bill.days_late), (75, 0));
+        assert_eq!(wallet, 1000);
+    }
+
+    #[test]
+    fn autopay_without_the_cash_runs_late() {
+        let mut bill = bill(true, 0);
+        let mut wallet = 10;
+        assert_eq!(bill.next_day(12, 31, &mut wallet), None);
+        assert_eq!(bill.next_day(13, 31, &mut wallet), Some(BillDay::Missed));
+        assert_eq!(wallet, 10);
+    }
+
+    #[test]
+    fn coming_due_again_keeps_counting_late_days() {
+        let mut bill = bill(false, 40);
+        bill.owed = 60;
+        bill.days_late = 30;
+        assert_eq!(bill.next_day(12, 31, &mut 0), None);
+        assert_eq!((bill.owed, bill.days_late), (120, 31));
+    }
+
+    #[test]
+    fn due_day_moves_to_the_end_of_short_months() {
+        let mut bill = Bill {
+            due_day: 31,
+            ..bill(false, 3)
+        };
+        assert_eq!(bill.next_day(28, 28, &mut 0), None);
+        assert_eq!(bill.owed, 60);
+    }
+}
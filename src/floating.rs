@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+
+use crate::{
+    events::{BillMissed, CashCollected, EnemyHit, PlayerHurt, RentMissed},
+    run::RunEntity,
+    ui::FONT,
+    Player, RENT_CREDIT,
+};
+
+const RISE_SPEED: f32 = 60.;
+const LIFETIME_SECS: f32 = 1.;
+
+/// A number drawn in the world that drifts up and fades out.
+#[derive(Component)]
+pub struct FloatingText {
+    timer: Timer,
+    color: Color,
+}
+
+fn spawn_floating_text(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    position: Vec3,
+    text: String,
+    color: Color,
+) {
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                text,
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: 32.0,
+                    color,
+                },
+            ),
+            // In front of sprites, a little above the thing it belongs to
+            transform: Transform::from_translation(Vec3::new(position.x, position.y + 40., 10.)),
+            ..default()
+        },
+        FloatingText {
+            timer: Timer::from_seconds(LIFETIME_SECS, TimerMode::Once),
+            color,
+        },
+        RunEntity,
+    ));
+}
+
+pub fn spawn_floating_numbers(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    transforms: Query<&Transform>,
+    player: Query<&Transform, With<Player>>,
+    mut hits: EventReader<EnemyHit>,
+    mut collected: EventReader<CashCollected>,
+    mut hurts: EventReader<PlayerHurt>,
+    mut rent_missed: EventReader<RentMissed>,
+    mut bills_missed: EventReader<BillMissed>,
+) {
+    for hit in hits.iter() {
+        if let Ok(t) = transforms.get(hit.enemy) {
+            let text = format!("{:.0}", hit.damage);
+            spawn_floating_text(
+                &mut commands,
+                &asset_server,
+                t.translation,
+                text,
+                Color::WHITE,
+            );
+        }
+    }
+    for cash in collected.iter() {
+        let text = format!("+${}", cash.amount);
+        spawn_floating_text(
+            &mut commands,
+            &asset_server,
+            cash.position,
+            text,
+            Color::GREEN,
+        );
+    }
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    for hurt in hurts.iter() {
+        let text = format!("-{:.0} credit", hurt.damage);
+        spawn_floating_text(
+            &mut commands,
+            &asset_server,
+            player.translation,
+            text,
+            Color::ORANGE_RED,
+        );
+    }
+    for _ in rent_missed.iter() {
+        let text = format!("-{:.0} credit", RENT_CREDIT);
+        spawn_floating_text(
+            &mut commands,
+            &asset_server,
+            player.translation,
+            text,
+            Color::ORANGE_RED,
+        );
+    }
+    for missed in bills_missed.iter() {
+        let text = format!("-{:.0} credit", missed.credit_penalty);
+        spawn_floating_text(
+            &mut commands,
+            &asset_server,
+            player.translation,
+            text,
+            Color::ORANGE_RED,
+        );
+    }
+}
+
+pub fn update_floating_text(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut texts: Query<(Entity, &mut FloatingText, &mut Transform, &mut Text)>,
+) {
+    for (entity, mut floating, mut t, mut text) in texts.iter_mut() {
+        floating.timer.tick(time.delta());
+        if floating.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        t.translation.y += RISE_SPEED * time.delta_seconds();
+        let alpha = floating.timer.percent_left();
+        text.sections[0].style.color = floating.color.with_a(alpha);
+    }
+}
@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ending::{Ending, RunStats},
+    menu::{MainMenuButton, SubMenu},
+    run::{Difficulty, GameRng},
+    ui::{overlay, spawn_button, spawn_sized_button, spawn_title, FONT},
+    CurrentHealth, Player,
+};
+
+const HISTORY_PATH: &str = "history.ron";
+/// Rows shown on the high score screen.
+const SHOWN_RUNS: usize = 10;
+const COLUMNS: [(&str, f32); 7] = [
+    ("Difficulty", 110.),
+    ("Months", 90.),
+    ("Peak Cash", 120.),
+    ("Credit", 90.),
+    ("Kills", 80.),
+    ("Ending", 140.),
+    ("Seed", 220.),
+];
+
+/// A finished run, kept forever in `history.ron`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    pub months: u32,
+    pub peak_cash: i32,
+    pub credit: f32,
+    pub kills: u32,
+    pub ending: Ending,
+}
+
+/// Every finished run, oldest first.
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct RunHistory(pub Vec<RunRecord>);
+
+impl RunHistory {
+    pub fn load() -> RunHistory {
+        let Ok(data) = std::fs::read_to_string(HISTORY_PATH) else {
+            return RunHistory::default();
+        };
+        ron::from_str(&data).unwrap_or_else(|e| {
+            println!("ignoring broken run history {HISTORY_PATH}: {e}");
+            RunHistory::default()
+        })
+    }
+
+    fn save(&self) {
+        match ron::ser::to_string_pretty(self, default()) {
+            Ok(data) => {
+                if let Err(e) = std::fs::write(HISTORY_PATH, data) {
+                    println!("failed to write run history {HISTORY_PATH}: {e}");
+                }
+            }
+            Err(e) => println!("failed to serialize run history: {e}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HistorySort {
+    Recent,
+    Months,
+    PeakCash,
+    Credit,
+    Kills,
+}
+
+impl HistorySort {
+    pub const ALL: [HistorySort; 5] = [
+        HistorySort::Recent,
+        HistorySort::Months,
+        HistorySort::PeakCash,
+        HistorySort::Credit,
+        HistorySort::Kills,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HistorySort::Recent => "Recent",
+            HistorySort::Months => "Months",
+            HistorySort::PeakCash => "Cash",
+            HistorySort::Credit => "Credit",
+            HistorySort::Kills => "Kills",
+        }
+    }
+
+    /// Best first, or newest first for `Recent`.
+    fn sorted(self, history: &RunHistory) -> Vec<&RunRecord> {
+        let mut runs: Vec<&RunRecord> = history.0.iter().rev().collect();
+        match self {
+            HistorySort::Recent => {}
+            HistorySort::Months => runs.sort_by_key(|run| std::cmp::Reverse(run.months)),
+            HistorySort::PeakCash => runs.sort_by_key(|run| std::cmp::Reverse(run.peak_cash)),
+            HistorySort::Credit => runs.sort_by(|a, b| b.credit.total_cmp(&a.credit)),
+            HistorySort::Kills => runs.sort_by_key(|run| std::cmp::Reverse(run.kills)),
+        }
+        runs
+    }
+}
+
+pub fn record_run(
+    mut history: ResMut<RunHistory>,
+    stats: Res<RunStats>,
+    rng: Res<GameRng>,
+    difficulty: Res<Difficulty>,
+    player: Query<&CurrentHealth, With<Player>>,
+) {
+    let Some(ending) = stats.ending else {
+        return;
+    };
+    history.0.push(RunRecord {
+        seed: rng.seed,
+        difficulty: *difficulty,
+        months: stats.months,
+        peak_cash: stats.peak_cash,
+        credit: player.get_single().map_or(0., |c| c.0),
+        kills: stats.kills,
+        ending,
+    });
+    history.save();
+}
+
+fn spawn_row(parent: &mut ChildBuilder, font: &Handle<Font>, cells: [String; 7], color: Color) {
+    parent.spawn(NodeBundle::default()).with_children(|row| {
+        for (text, (_, width)) in cells.into_iter().zip(COLUMNS) {
+            row.spawn(
+                TextBundle::from_section(
+                    text,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 22.0,
+                        color,
+                    },
+                )
+                .with_style(Style {
+                    size: Size::width(Val::Px(width)),
+                    ..default()
+                }),
+            );
+        }
+    });
+}
+
+pub fn open_history(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    history: &RunHistory,
+    sort: HistorySort,
+) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    commands
+        .spawn((overlay(20), SubMenu))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), "High Scores");
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        gap: Size::width(Val::Px(8.)),
+                        margin: UiRect::bottom(Val::Px(10.)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|sorts| {
+                    for choice in HistorySort::ALL {
+                        let label = if choice == sort {
+                            format!("> {}", choice.label())
+                        } else {
+                            choice.label().to_string()
+                        };
+                        spawn_sized_button(
+                            sorts,
+                            font.clone(),
+                            &label,
+                            Size::new(Val::Px(140.), Val::Px(44.)),
+                            MainMenuButton::SortHistory(choice),
+                        );
+                    }
+                });
+
+            spawn_row(
+                parent,
+                &font,
+                COLUMNS.map(|(name, _)| name.to_string()),
+                Color::GOLD,
+            );
+            let runs = sort.sorted(history);
+            if runs.is_empty() {
+                parent.spawn(TextBundle::from_section(
+                    "No finished runs yet",
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 22.0,
+                        color: Color::GRAY,
+                    },
+                ));
+            }
+            for run in runs.into_iter().take(SHOWN_RUNS) {
+                let color = if run.ending.is_win() {
+                    Color::WHITE
+                } else {
+                    Color::GRAY
+                };
+                spawn_row(
+                    parent,
+                    &font,
+                    [
+                        run.difficulty.label().to_string(),
+                        run.months.to_string(),
+                        format!("${}", run.peak_cash),
+                        format!("{:.0}", run.credit),
+                        run.kills.to_string(),
+                        run.ending.title().to_string(),
+                        format!("{:016x}", run.seed),
+                    ],
+                    color,
+                );
+            }
+            spawn_button(parent, font, "Back", MainMenuButton::Back);
+        });
+}
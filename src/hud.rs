@@ -0,0 +1,324 @@
+use bevy::prelude::*;
+
+use crate::{
+    calendar::Calendar,
+    expenses::Bills,
+    loot::Coupons,
+    pickups::Magnet,
+    powerups::{DoubleBounty, PowerUpKind, RapidFire, Shield, SlowEnemies},
+    run::RunEntity,
+    ui::FONT,
+    CurrentCard, CurrentHealth, MaxHealth, MoneyText, Player, CREDIT_FLOOR,
+};
+
+pub const CARD_NAMES: [&str; 2] = ["Basic Card", "Gold Card"];
+
+const CREDIT_BAR_WIDTH: f32 = 240.;
+/// Slowest the cash counter rolls, in dollars per second.
+const COUNT_UP_MIN_SPEED: f32 = 200.;
+
+#[derive(Component)]
+pub struct CreditBarFill;
+
+#[derive(Component)]
+pub struct CreditText;
+
+/// Shown cash rolls towards the wallet in `MoneyText` instead of jumping.
+#[derive(Component)]
+pub struct CashText {
+    shown: f32,
+}
+
+#[derive(Component)]
+pub struct RentText;
+
+#[derive(Component)]
+pub struct DayText;
+
+#[derive(Component)]
+pub struct MonthText;
+
+#[derive(Component)]
+pub struct CardText;
+
+#[derive(Component)]
+pub struct MagnetText;
+
+/// Shown while the player has the power-up, with the seconds it has left.
+#[derive(Component)]
+pub struct PowerUpIcon(PowerUpKind);
+
+fn label(font: &Handle<Font>, text: &str, size: f32, color: Color) -> TextBundle {
+    TextBundle::from_section(
+        text,
+        TextStyle {
+            font: font.clone(),
+            font_size: size,
+            color,
+        },
+    )
+}
+
+fn column(align_items: AlignItems) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Column,
+            align_items,
+            gap: Size::height(Val::Px(4.)),
+            ..default()
+        },
+        ..default()
+    }
+}
+
+/// HUD for a run. The wallet lives on the cash text so `MoneyText` queries keep working.
+pub fn spawn_hud(commands: &mut Commands, asset_server: &AssetServer, cash: i32, rent: i32) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::SpaceBetween,
+                    padding: UiRect::all(Val::Px(12.)),
+                    ..default()
+                },
+                ..default()
+            },
+            RunEntity,
+        ))
+        .with_children(|root| {
+            root.spawn(NodeBundle {
+                style: Style {
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::FlexStart,
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|top| {
+                // Credit score
+                top.spawn(column(AlignItems::FlexStart))
+                    .with_children(|credit| {
+                        credit.spawn(label(&font, "CREDIT SCORE", 20., Color::WHITE));
+                        credit
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(CREDIT_BAR_WIDTH), Val::Px(18.)),
+                                    ..default()
+                                },
+                                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                                ..default()
+                            })
+                            .with_children(|bar| {
+                                bar.spawn((
+                                    NodeBundle {
+                                        style: Style {
+                                            size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                                            ..default()
+                                        },
+                                        background_color: Color::rgb(0.9, 0.1, 0.1).into(),
+                                        ..default()
+                                    },
+                                    CreditBarFill,
+                                ));
+                            });
+                        credit.spawn((label(&font, "", 24., Color::WHITE), CreditText));
+                        credit
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    gap: Size::width(Val::Px(6.)),
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|icons| {
+                                for kind in PowerUpKind::ALL {
+                                    icons
+                                        .spawn((
+                                            NodeBundle {
+                                                style: Style {
+                                                    display: Display::None,
+                                                    padding: UiRect::all(Val::Px(4.)),
+                                                    ..default()
+                                                },
+                                                background_color: kind.color().with_a(0.8).into(),
+                                                ..default()
+                                            },
+                                            PowerUpIcon(kind),
+                                        ))
+                                        .with_children(|icon| {
+                                            icon.spawn(label(&font, "", 18., Color::BLACK));
+                                        });
+                                }
+                            });
+                    });
+
+                // Calendar
+                top.spawn(column(AlignItems::Center))
+                    .with_children(|calendar| {
+                        calendar.spawn((label(&font, "", 24., Color::WHITE), MonthText));
+                        calendar.spawn((label(&font, "", 36., Color::ORANGE_RED), DayText));
+                    });
+
+                // Money
+                top.spawn(column(AlignItems::FlexEnd))
+                    .with_children(|money| {
+                        money.spawn((
+                            label(&font, "", 32., Color::GOLD),
+                            CashText { shown: cash as f32 },
+                            MoneyText(cash, rent),
+                        ));
+                        money.spawn((label(&font, "", 20., Color::WHITE), RentText));
+                    });
+            });
+
+            root.spawn(NodeBundle {
+                style: Style {
+                    gap: Size::width(Val::Px(32.)),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|bottom| {
+                bottom.spawn((label(&font, "", 22., Color::WHITE), CardText));
+                bottom.spawn((label(&font, "", 22., Color::WHITE), MagnetText));
+            });
+        });
+}
+
+pub fn update_credit_bar(
+    player: Query<(&CurrentHealth, &MaxHealth), With<Player>>,
+    mut fill: Query<(&mut Style, &mut BackgroundColor), With<CreditBarFill>>,
+    mut text: Query<&mut Text, With<CreditText>>,
+) {
+    let Ok((credit, max)) = player.get_single() else {
+        return;
+    };
+    let fraction = ((credit.0 - CREDIT_FLOOR) / (max.0 - CREDIT_FLOOR)).clamp(0., 1.);
+    for (mut style, mut color) in fill.iter_mut() {
+        style.size.width = Val::Percent(fraction * 100.);
+        // Red when close to bankrupt, green near a perfect score
+        *color = Color::rgb(0.9 * (1. - fraction), 0.2 + 0.6 * fraction, 0.1).into();
+    }
+    for mut text in text.iter_mut() {
+        text.sections[0].value = format!("{:.0} / {:.0}", credit.0, max.0);
+    }
+}
+
+pub fn update_cash_text(time: Res<Time>, mut query: Query<(&MoneyText, &mut CashText, &mut Text)>) {
+    for (money, mut cash, mut text) in query.iter_mut() {
+        let target = money.0 as f32;
+        let diff = target - cash.shown;
+        let step = (diff.abs() * 4.).max(COUNT_UP_MIN_SPEED) * time.delta_seconds();
+        cash.shown = if diff.abs() <= step {
+            target
+        } else {
+            cash.shown + diff.signum() * step
+        };
+        text.sections[0].value = format!("${:.0}", cash.shown);
+    }
+}
+
+pub fn update_rent_text(
+    calendar: Res<Calendar>,
+    bills: Res<Bills>,
+    coupons: Res<Coupons>,
+    money: Query<&MoneyText>,
+    mut text: Query<&mut Text, With<RentText>>,
+) {
+    let Ok(money) = money.get_single() else {
+        return;
+    };
+    let days_left = calendar.days_left().ceil();
+    for mut text in text.iter_mut() {
+        let owed = bills.total_owed();
+        let rent = (money.1 - coupons.0).max(0);
+        let mut value = format!("Rent due: ${} in {} days", rent, days_left);
+        if coupons.0 > 0 {
+            value += &format!("\nCoupons: -${}", coupons.0);
+        }
+        if owed > 0 {
+            value += &format!("\nBills owed: ${}", owed);
+        }
+        text.sections[0].value = value;
+        text.sections[0].style.color = if money.0 < rent + owed {
+            Color::ORANGE_RED
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+pub fn update_calendar_text(
+    calendar: Res<Calendar>,
+    mut days: Query<&mut Text, (With<DayText>, Without<MonthText>)>,
+    mut months: Query<&mut Text, (With<MonthText>, Without<DayText>)>,
+) {
+    for mut text in days.iter_mut() {
+        text.sections[0].value = format!("{} {}", calendar.weekday.short_name(), calendar.day);
+    }
+    for mut text in months.iter_mut() {
+        text.sections[0].value = format!("{} {}", calendar.month_name(), calendar.year);
+    }
+}
+
+pub fn update_card_text(card: Res<CurrentCard>, mut text: Query<&mut Text, With<CardText>>) {
+    let name = CARD_NAMES[(card.0 as usize).min(CARD_NAMES.len() - 1)];
+    for mut text in text.iter_mut() {
+        text.sections[0].value = if card.0 == 0 {
+            format!("{}  [Q] upgrade for $1000", name)
+        } else {
+            name.to_string()
+        };
+    }
+}
+
+pub fn update_magnet_text(magnet: Res<Magnet>, mut text: Query<&mut Text, With<MagnetText>>) {
+    for mut text in text.iter_mut() {
+        text.sections[0].value = match magnet.next_cost() {
+            Some(cost) if magnet.level == 0 => format!("No Magnet  [E] buy for ${}", cost),
+            Some(cost) => format!("Magnet {}  [E] upgrade for ${}", magnet.level, cost),
+            None => format!("Magnet {}", magnet.level),
+        };
+    }
+}
+
+pub fn update_power_up_icons(
+    player: Query<
+        (
+            Option<&DoubleBounty>,
+            Option<&RapidFire>,
+            Option<&Shield>,
+            Option<&SlowEnemies>,
+        ),
+        With<Player>,
+    >,
+    mut icons: Query<(&PowerUpIcon, &mut Style, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    let Ok((double_bounty, rapid_fire, shield, slow_enemies)) = player.get_single() else {
+        return;
+    };
+    for (icon, mut style, children) in icons.iter_mut() {
+        let timer = match icon.0 {
+            PowerUpKind::DoubleBounty => double_bounty.map(|p| &p.0),
+            PowerUpKind::RapidFire => rapid_fire.map(|p| &p.timer),
+            PowerUpKind::Shield => shield.map(|p| &p.0),
+            PowerUpKind::SlowEnemies => slow_enemies.map(|p| &p.0),
+        };
+        let Some(timer) = timer else {
+            style.display = Display::None;
+            continue;
+        };
+        style.display = Display::Flex;
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value =
+                    format!("{} {:.0}s", icon.0.icon(), timer.remaining_secs().ceil());
+            }
+        }
+    }
+}
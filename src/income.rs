@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calendar::Weekday,
+    config::load_config,
+    events::{DayStarted, EnemyKilled, PaycheckReceived, PlayerHurt},
+    toast::{ToastKind, Toasts},
+    MoneyText,
+};
+
+const JOB_CONFIG: &str = "config/job.ron";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum PaySchedule {
+    Weekly,
+    Biweekly,
+}
+
+impl PaySchedule {
+    fn paydays_per_check(self) -> u32 {
+        match self {
+            PaySchedule::Weekly => 1,
+            PaySchedule::Biweekly => 2,
+        }
+    }
+}
+
+#[derive(Resource, Deserialize)]
+#[serde(default)]
+pub struct Job {
+    pub title: String,
+    pub schedule: PaySchedule,
+    pub payday: Weekday,
+    pub base_pay: i32,
+    pub bonus_per_kill: i32,
+    pub penalty_per_hit: i32,
+    pub min_pay: i32,
+    pub max_pay: i32,
+}
+
+impl Default for Job {
+    fn default() -> Self {
+        Self {
+            title: "Bill Collector".to_string(),
+            schedule: PaySchedule::Biweekly,
+            payday: Weekday::Friday,
+            base_pay: 1000,
+            bonus_per_kill: 15,
+            penalty_per_hit: 25,
+            min_pay: 500,
+            max_pay: 2000,
+        }
+    }
+}
+
+impl Job {
+    pub fn load() -> Job {
+        load_config(JOB_CONFIG)
+    }
+
+    pub fn paycheck(&self, period: &PayPeriod) -> i32 {
+        let pay = self.base_pay + self.bonus_per_kill * period.kills as i32
+            - self.penalty_per_hit * period.hits as i32;
+        pay.clamp(self.min_pay, self.max_pay)
+    }
+}
+
+/// Performance since the last paycheck.
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PayPeriod {
+    pub kills: u32,
+    pub hits: u32,
+    pub paydays: u32,
+}
+
+pub fn track_performance(
+    mut period: ResMut<PayPeriod>,
+    mut kills: EventReader<EnemyKilled>,
+    mut hurts: EventReader<PlayerHurt>,
+) {
+    for _ in kills.iter() {
+        period.kills += 1;
+    }
+    for _ in hurts.iter() {
+        period.hits += 1;
+    }
+}
+
+pub fn pay_salary(
+    job: Res<Job>,
+    mut period: ResMut<PayPeriod>,
+    mut days: EventReader<DayStarted>,
+    mut money: Query<&mut MoneyText>,
+    mut toasts: ResMut<Toasts>,
+    mut paychecks: EventWriter<PaycheckReceived>,
+) {
+    for day in days.iter() {
+        if day.weekday != job.payday {
+            continue;
+        }
+        period.paydays += 1;
+        if period.paydays < job.schedule.paydays_per_check() {
+            continue;
+        }
+        let amount = job.paycheck(&period);
+        money.single_mut().0 += amount;
+        toasts.notify(
+            ToastKind::Success,
+            format!("{} PAYCHECK: +${}", job.title.to_uppercase(), amount),
+            3.,
+        );
+        paychecks.send(PaycheckReceived { amount });
+        *period = PayPeriod::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period(kills: u32, hits: u32) -> PayPeriod {
+        PayPeriod {
+            kills,
+            hits,
+            paydays: 0,
+        }
+    }
+
+    #[test]
+    fn paycheck_adds_kills_and_takes_off_hits() {
+        let job = Job::default();
+        assert_eq!(job.paycheck(&period(0, 0)), 1000);
+        assert_eq!(job.paycheck(&period(10, 2)), 1000 + 150 - 50);
+    }
+
+    #[test]
+    fn paycheck_stays_between_min_and_max() {
+        let job = Job::default();
+        assert_eq!(job.paycheck(&period(0, 100)), job.min_pay);
+        assert_eq!(job.paycheck(&period(1000, 0)), job.max_pay);
+    }
+}
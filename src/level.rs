@@ -0,0 +1,216 @@
+use bevy::prelude::*;
+use bevy_rapier2d::geometry::{Collider, Sensor};
+use serde::Deserialize;
+
+use crate::{
+    parallax::{spawn_background_layer, BackgroundLayerData},
+    run::RunEntity,
+    ContactDamage, Player, PLAYER_HALF_SIZE,
+};
+
+/// Stage name and level file, in the order the stage picker lists them.
+pub const LEVELS: [(&str, &str); 2] = [
+    ("City Streets", "levels/city.ron"),
+    ("Rooftops", "levels/rooftops.ron"),
+];
+
+#[derive(Resource, Default)]
+pub struct CurrentLevel(pub usize);
+
+#[derive(Deserialize)]
+pub struct LevelData {
+    /// Parallax layers from back to front.
+    pub background: Vec<BackgroundLayerData>,
+    pub player_start: (f32, f32),
+    /// Bottom-left and top-right corners the camera and player are kept inside of.
+    pub bounds: ((f32, f32), (f32, f32)),
+    pub platforms: Vec<PlatformData>,
+    #[serde(default)]
+    pub hazards: Vec<HazardData>,
+    pub enemy_spawns: Vec<EnemySpawnData>,
+}
+
+#[derive(Deserialize)]
+pub struct PlatformData {
+    pub position: (f32, f32),
+    pub size: (f32, f32),
+    /// Can be jumped through from below and dropped through with S.
+    #[serde(default)]
+    pub one_way: bool,
+    /// Platforms without a color are invisible, e.g. ground drawn by the background.
+    #[serde(default)]
+    pub color: Option<(f32, f32, f32)>,
+}
+
+#[derive(Deserialize)]
+pub struct HazardData {
+    pub position: (f32, f32),
+    pub size: (f32, f32),
+    pub damage: f32,
+    pub color: (f32, f32, f32),
+}
+
+#[derive(Deserialize)]
+pub struct EnemySpawnData {
+    pub position: (f32, f32),
+    /// Random offset range around `position` for each spawn.
+    #[serde(default)]
+    pub spread: (f32, f32),
+    /// Spawn an enemy here as soon as the level loads.
+    #[serde(default)]
+    pub initial: bool,
+}
+
+#[derive(Component)]
+pub struct OneWayPlatform {
+    pub top: f32,
+}
+
+#[derive(Component)]
+pub struct Hazard;
+
+#[derive(Component)]
+pub struct EnemySpawnPoint {
+    pub spread: Vec2,
+}
+
+impl LevelData {
+    /// Bare floor with one spawn point, used when a level file can't be loaded.
+    pub fn fallback() -> LevelData {
+        LevelData {
+            background: Vec::new(),
+            player_start: (0., 0.),
+            bounds: ((-1000., -300.), (1000., 600.)),
+            platforms: vec![PlatformData {
+                position: (0., -280.),
+                size: (2000., 40.),
+                one_way: false,
+                color: Some((0.3, 0.3, 0.3)),
+            }],
+            hazards: Vec::new(),
+            enemy_spawns: vec![EnemySpawnData {
+                position: (500., 0.),
+                spread: (100., 0.),
+                initial: true,
+            }],
+        }
+    }
+}
+
+pub fn load_level(path: &str) -> Result<LevelData, String> {
+    let file = format!("assets/{path}");
+    let data =
+        std::fs::read_to_string(&file).map_err(|e| format!("failed to read level {file}: {e}"))?;
+    ron::from_str(&data).map_err(|e| format!("failed to parse level {file}: {e}"))
+}
+
+pub fn spawn_level(commands: &mut Commands, asset_server: &AssetServer, level: &LevelData) {
+    // Background
+    for (index, layer) in level.background.iter().enumerate() {
+        spawn_background_layer(commands, asset_server, layer, index);
+    }
+
+    for platform in level.platforms.iter() {
+        let size = Vec2::new(platform.size.0, platform.size.1);
+        let transform =
+            Transform::from_translation(Vec3::new(platform.position.0, platform.position.1, 0.5));
+        let mut entity = match platform.color {
+            Some((r, g, b)) => commands.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(r, g, b),
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform,
+                ..default()
+            }),
+            None => commands.spawn(TransformBundle::from_transform(transform)),
+        };
+        entity.insert((Collider::cuboid(size.x / 2., size.y / 2.), RunEntity));
+        if platform.one_way {
+            entity.insert((
+                OneWayPlatform {
+                    top: platform.position.1 + size.y / 2.,
+                },
+                Sensor,
+            ));
+        }
+    }
+
+    // Walls at the level edges
+    let ((min_x, min_y), (max_x, max_y)) = level.bounds;
+    let half_height = (max_y - min_y) / 2.;
+    for x in [min_x - 10., max_x + 10.] {
+        commands.spawn((
+            TransformBundle::from_transform(Transform::from_translation(Vec3::new(
+                x,
+                min_y + half_height,
+                0.,
+            ))),
+            Collider::cuboid(10., half_height),
+            RunEntity,
+        ));
+    }
+
+    for hazard in level.hazards.iter() {
+        let size = Vec2::new(hazard.size.0, hazard.size.1);
+        let (r, g, b) = hazard.color;
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(r, g, b),
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(Vec3::new(
+                    hazard.position.0,
+                    hazard.position.1,
+                    0.5,
+                )),
+                ..default()
+            },
+            Collider::cuboid(size.x / 2., size.y / 2.),
+            Sensor,
+            Hazard,
+            ContactDamage(hazard.damage),
+            RunEntity,
+        ));
+    }
+
+    for spawn in level.enemy_spawns.iter() {
+        commands.spawn((
+            TransformBundle::from_transform(Transform::from_translation(Vec3::new(
+                spawn.position.0,
+                spawn.position.1,
+                1.,
+            ))),
+            EnemySpawnPoint {
+                spread: Vec2::new(spawn.spread.0, spawn.spread.1),
+            },
+            RunEntity,
+        ));
+    }
+}
+
+/// One-way platforms are only solid while the player is above them and not holding S.
+pub fn update_one_way_platforms(
+    player: Query<&Transform, With<Player>>,
+    platforms: Query<(Entity, &OneWayPlatform, Option<&Sensor>)>,
+    key_input: Res<Input<KeyCode>>,
+    mut commands: Commands,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    let feet = player.translation.y - PLAYER_HALF_SIZE.y;
+    let dropping = key_input.pressed(KeyCode::S);
+
+    for (entity, platform, sensor) in platforms.iter() {
+        let solid = !dropping && feet >= platform.top - 2.;
+        if solid && sensor.is_some() {
+            commands.entity(entity).remove::<Sensor>();
+        } else if !solid && sensor.is_none() {
+            commands.entity(entity).insert(Sensor);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::load_config,
+    ending::RunStats,
+    events::DayStarted,
+    run::{GameRng, RunModifiers},
+    toast::{ToastKind, Toasts},
+    ui::{overlay, spawn_button, spawn_title, ButtonPressed, InputGrace, FONT},
+    CurrentHealth, GameState, MaxHealth, MoneyText, Player,
+};
+
+const LIFE_EVENTS: &str = "config/life_events.ron";
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct Conditions {
+    /// Months completed in the run.
+    pub min_month: u32,
+    pub min_cash: Option<i32>,
+    pub max_cash: Option<i32>,
+    pub min_credit: Option<f32>,
+    pub max_credit: Option<f32>,
+    /// Only ever happens once per run.
+    pub once: bool,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct Outcome {
+    pub cash: i32,
+    /// Added to the monthly rent for good.
+    pub rent: i32,
+    pub credit: f32,
+    /// Multiplies how often enemies spawn for the rest of the run.
+    pub spawn_rate: f32,
+    /// Cash that comes back at the start of next month.
+    pub repaid: i32,
+    pub message: String,
+}
+
+impl Default for Outcome {
+    fn default() -> Self {
+        Self {
+            cash: 0,
+            rent: 0,
+            credit: 0.,
+            spawn_rate: 1.,
+            repaid: 0,
+            message: String::new(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Choice {
+    pub label: String,
+    pub outcome: Outcome,
+}
+
+#[derive(Deserialize)]
+pub struct LifeEventData {
+    pub id: String,
+    pub title: String,
+    pub text: String,
+    pub weight: u32,
+    #[serde(default)]
+    pub conditions: Conditions,
+    pub choices: Vec<Choice>,
+}
+
+#[derive(Resource, Deserialize, Default)]
+pub struct LifeEvents {
+    /// Odds of an event on any given day once the cooldown is over.
+    pub chance_per_day: f64,
+    pub min_days_between: u32,
+    pub events: Vec<LifeEventData>,
+}
+
+impl LifeEvents {
+    pub fn load() -> LifeEvents {
+        let mut events: LifeEvents = load_config(LIFE_EVENTS);
+        // An event without choices would open a modal nothing can close
+        events.events.retain(|event| {
+            if event.choices.is_empty() {
+                println!("ignoring life event {} with no choices", event.id);
+            }
+            !event.choices.is_empty()
+        });
+        if !(0. ..=1.).contains(&events.chance_per_day) {
+            println!(
+                "life event chance_per_day {} isn't between 0 and 1",
+                events.chance_per_day
+            );
+            events.chance_per_day = if events.chance_per_day > 1. { 1. } else { 0. };
+        }
+        events
+    }
+}
+
+/// Per-run bookkeeping: the cooldown, which one-off events are used up and what's on screen.
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifeEventState {
+    pub days_since_last: u32,
+    pub seen: Vec<String>,
+    /// Owed back to the player on the first of next month.
+    pub repayment: i32,
+    #[serde(skip)]
+    pub active: Option<usize>,
+}
+
+impl Conditions {
+    fn met(&self, stats: &RunStats, cash: i32, credit: f32) -> bool {
+        stats.months >= self.min_month
+            && self.min_cash.map_or(true, |min| cash >= min)
+            && self.max_cash.map_or(true, |max| cash <= max)
+            && self.min_credit.map_or(true, |min| credit >= min)
+            && self.max_credit.map_or(true, |max| credit <= max)
+    }
+}
+
+pub fn roll_life_events(
+    events: Res<LifeEvents>,
+    mut state: ResMut<LifeEventState>,
+    mut rng: ResMut<GameRng>,
+    stats: Res<RunStats>,
+    mut money: Query<&mut MoneyText>,
+    player: Query<&CurrentHealth, With<Player>>,
+    mut days: EventReader<DayStarted>,
+    mut toasts: ResMut<Toasts>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let (Ok(mut money), Ok(credit)) = (money.get_single_mut(), player.get_single()) else {
+        return;
+    };
+    // The run is already over, don't pull up a modal over the ending
+    if stats.ending.is_some() {
+        return;
+    }
+    for day in days.iter() {
+        state.days_since_last += 1;
+        if day.day == 1 && state.repayment > 0 {
+            money.0 += state.repayment;
+            toasts.notify(
+                ToastKind::Success,
+                format!("PAID BACK: +${}", state.repayment),
+                3.,
+            );
+            state.repayment = 0;
+        }
+        // The first of the month belongs to the perk screen
+        if day.day == 1
+            || state.days_since_last < events.min_days_between
+            || !rng.gen_bool(events.chance_per_day)
+        {
+            continue;
+        }
+        let eligible: Vec<usize> = (0..events.events.len())
+            .filter(|i| {
+                let event = &events.events[*i];
+                event.conditions.met(&stats, money.0, credit.0)
+                    && !(event.conditions.once && state.seen.contains(&event.id))
+            })
+            .collect();
+        let Ok(index) = eligible.choose_weighted(&mut **rng, |i| events.events[*i].weight) else {
+            continue;
+        };
+        let event = &events.events[*index];
+        state.days_since_last = 0;
+        state.seen.push(event.id.clone());
+        state.active = Some(*index);
+        next_state.set(GameState::LifeEvent);
+        break;
+    }
+}
+
+#[derive(Component)]
+pub struct LifeEventModal;
+
+#[derive(Component, Clone, Copy)]
+pub struct ChoiceButton(pub usize);
+
+pub fn open_life_event(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    events: Res<LifeEvents>,
+    state: Res<LifeEventState>,
+) {
+    let Some(event) = state.active.map(|i| &events.events[i]) else {
+        return;
+    };
+    let font: Handle<Font> = asset_server.load(FONT);
+    commands
+        .spawn((overlay(10), LifeEventModal, InputGrace::default()))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), &event.title);
+            parent.spawn(
+                TextBundle::from_section(
+                    event.text.as_str(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 28.0,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_style(Style {
+                    max_size: Size::width(Val::Px(700.)),
+                    margin: UiRect::bottom(Val::Px(20.)),
+                    ..default()
+                }),
+            );
+            for (index, choice) in event.choices.iter().enumerate() {
+                spawn_button(parent, font.clone(), &choice.label, ChoiceButton(index));
+            }
+        });
+}
+
+pub fn close_life_event(mut commands: Commands, modals: Query<Entity, With<LifeEventModal>>) {
+    for modal in modals.iter() {
+        commands.entity(modal).despawn_recursive();
+    }
+}
+
+pub fn life_event_choices(
+    mut pressed: EventReader<ButtonPressed>,
+    buttons: Query<&ChoiceButton>,
+    events: Res<LifeEvents>,
+    mut state: ResMut<LifeEventState>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut money: Query<&mut MoneyText>,
+    mut player: Query<(&mut CurrentHealth, &MaxHealth), With<Player>>,
+    mut toasts: ResMut<Toasts>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(button) = pressed.iter().find_map(|p| buttons.get(p.0).ok()) else {
+        return;
+    };
+    let Some(event) = state.active.take().map(|i| &events.events[i]) else {
+        return;
+    };
+    let outcome = &event.choices[button.0].outcome;
+    let mut wallet = money.single_mut();
+    wallet.0 += outcome.cash;
+    wallet.1 += outcome.rent;
+    let (mut credit, max) = player.single_mut();
+    credit.0 = (credit.0 + outcome.credit).min(max.0);
+    modifiers.spawn_rate *= outcome.spawn_rate;
+    state.repayment += outcome.repaid;
+
+    let kind = if outcome.cash < 0 || outcome.rent > 0 || outcome.credit < 0. {
+        ToastKind::Warning
+    } else {
+        ToastKind::Info
+    };
+    toasts.notify(kind, outcome.message.as_str(), 3.);
+    next_state.set(GameState::Playing);
+}
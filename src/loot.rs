@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::load_config,
+    events::EnemyKilled,
+    pickups::{spawn_pickup, PickupItem, COIN_VALUE},
+    powerups::PowerUpKind,
+    run::{Difficulty, GameRng, RunModifiers},
+    CurrentCard, ENEMY_KINDS,
+};
+
+const DROP_TABLES: &str = "config/drops.ron";
+/// Cash drops are worth this much more with each card tier.
+const CARD_CASH_BONUS: f32 = 0.5;
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub enum Loot {
+    Cash {
+        min: i32,
+        max: i32,
+    },
+    /// Knocks `amount` off the next rent.
+    Coupon {
+        amount: i32,
+    },
+    /// Unlocks a better card. Never drops once the player has it.
+    Card {
+        tier: i32,
+    },
+    PowerUp {
+        kind: PowerUpKind,
+        secs: f32,
+    },
+}
+
+#[derive(Deserialize)]
+pub struct Drop {
+    pub chance: f32,
+    pub loot: Loot,
+}
+
+impl Drop {
+    /// Whether `roll` can use this entry without panicking.
+    fn valid(&self) -> bool {
+        let loot_ok = match self.loot {
+            Loot::Cash { min, max } => min <= max,
+            Loot::PowerUp { secs, .. } => secs > 0.,
+            Loot::Coupon { .. } | Loot::Card { .. } => true,
+        };
+        self.chance >= 0. && loot_ok
+    }
+}
+
+/// Drop tables keyed by enemy kind.
+#[derive(Resource, Deserialize)]
+#[serde(transparent)]
+pub struct DropTables(pub HashMap<String, Vec<Drop>>);
+
+/// Bills still pay out without a config, they just never drop anything special.
+impl Default for DropTables {
+    fn default() -> Self {
+        let cash = Drop {
+            chance: 1.,
+            loot: Loot::Cash { min: 80, max: 120 },
+        };
+        Self(HashMap::from([("bill".to_string(), vec![cash])]))
+    }
+}
+
+impl DropTables {
+    pub fn load() -> DropTables {
+        let tables: DropTables = load_config(DROP_TABLES);
+        tables.validated()
+    }
+
+    /// Drops entries `roll` can't use and reports enemy kinds that won't drop anything.
+    fn validated(mut self) -> DropTables {
+        for (kind, table) in self.0.iter_mut() {
+            table.retain(|drop| {
+                if !drop.valid() {
+                    println!(
+                        "ignoring bad {kind} drop: chance {}, {:?}",
+                        drop.chance, drop.loot
+                    );
+                }
+                drop.valid()
+            });
+        }
+        for kind in ENEMY_KINDS {
+            if !self.0.contains_key(kind) {
+                println!("no drop table for {kind}, it won't drop anything");
+            }
+        }
+        self
+    }
+
+    /// Rolls every entry in `kind`'s table. `drop_rate` scales the chances that aren't certain.
+    pub fn roll(
+        &self,
+        kind: &str,
+        rng: &mut impl Rng,
+        drop_rate: f32,
+        card: i32,
+    ) -> Vec<PickupItem> {
+        let Some(table) = self.0.get(kind) else {
+            return Vec::new();
+        };
+        let mut items = Vec::new();
+        for drop in table.iter() {
+            let chance = if drop.chance >= 1. {
+                1.
+            } else {
+                (drop.chance * drop_rate).min(1.)
+            };
+            if !rng.gen_bool(chance as f64) {
+                continue;
+            }
+            match drop.loot {
+                Loot::Cash { min, max } => {
+                    let bonus = 1. + CARD_CASH_BONUS * card as f32;
+                    let amount = (rng.gen_range(min..=max) as f32 * bonus) as i32;
+                    items.push(PickupItem::Cash(amount));
+                }
+                Loot::Coupon { amount } => items.push(PickupItem::Coupon(amount)),
+                Loot::Card { tier } if tier > card => items.push(PickupItem::Card(tier)),
+                Loot::Card { .. } => {}
+                Loot::PowerUp { kind, secs } => items.push(PickupItem::PowerUp(kind, secs)),
+            }
+        }
+        items
+    }
+}
+
+/// Rent discount from coupons, used up at the next rent payment.
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct Coupons(pub i32);
+
+pub fn drop_loot(
+    mut commands: Commands,
+    mut kills: EventReader<EnemyKilled>,
+    tables: Res<DropTables>,
+    mut rng: ResMut<GameRng>,
+    difficulty: Res<Difficulty>,
+    modifiers: Res<RunModifiers>,
+    card: Res<CurrentCard>,
+) {
+    let drop_rate = difficulty.drop_rate() * modifiers.luck;
+    for kill in kills.iter() {
+        for item in tables.roll(kill.kind, &mut **rng, drop_rate, card.0) {
+            // Cash comes out as a spray of coins
+            if let PickupItem::Cash(amount) = item {
+                let mut left = (amount as f32 * kill.cash_multiplier * modifiers.bounty) as i32;
+                while left > 0 {
+                    let value = left.min(COIN_VALUE);
+                    left -= value;
+                    spawn_pickup(
+                        &mut commands,
+                        &mut **rng,
+                        kill.position,
+                        PickupItem::Cash(value),
+                    );
+                }
+            } else {
+                spawn_pickup(&mut commands, &mut **rng, kill.position, item);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drop(chance: f32, loot: Loot) -> Drop {
+        Drop { chance, loot }
+    }
+
+    #[test]
+    fn bad_drops_are_removed_at_load() {
+        let tables = DropTables(HashMap::from([(
+            "bill".to_string(),
+            vec![
+                drop(1., Loot::Cash { min: 80, max: 120 }),
+                drop(-0.5, Loot::Coupon { amount: 100 }),
+                drop(f32::NAN, Loot::Card { tier: 1 }),
+                drop(0.5, Loot::Cash { min: 120, max: 80 }),
+                drop(
+                    0.5,
+                    Loot::PowerUp {
+                        kind: PowerUpKind::Shield,
+                        secs: 0.,
+                    },
+                ),
+            ],
+        )]))
+        .validated();
+
+        let table = &tables.0["bill"];
+        assert_eq!(table.len(), 1);
+        assert!(matches!(table[0].loot, Loot::Cash { min: 80, max: 120 }));
+    }
+
+    #[test]
+    fn missing_tables_drop_nothing() {
+        let tables = DropTables(HashMap::new()).validated();
+        let mut rng = rand::thread_rng();
+        assert!(tables.roll("bill", &mut rng, 1., 0).is_empty());
+    }
+}
@@ -1,6 +1,14 @@
-use bevy::{asset, prelude::*, sprite::Anchor, window::PrimaryWindow};
+use achievements::check_achievements;
+use animation::{
+    animate_sprites, enemy_animation_state, load_animations, player_animation_state, AnimationClip,
+    AnimationLibrary, Animator, Dying,
+};
+use audio::{
+    play_sfx, queue_sfx, toggle_mute, update_music, AudioSettings, CurrentMusic, SfxQueue,
+};
+use bevy::{asset, prelude::*, window::PrimaryWindow};
 use bevy_rapier2d::{
-    control::KinematicCharacterController,
+    control::{KinematicCharacterController, KinematicCharacterControllerOutput},
     dynamics::RigidBody,
     geometry::{Collider, Sensor},
     na::Translation,
@@ -8,45 +16,307 @@ use bevy_rapier2d::{
     plugin::{NoUserData, RapierConfiguration, RapierContext, RapierPhysicsPlugin},
     render::RapierDebugRenderPlugin,
 };
-use rand::Rng;
+use calendar::{tick_calendar, Calendar, CalendarConfig};
+use camera::{camera_follow, CameraFollow, LevelBounds, MainCamera};
+use config::load_config;
+use display::{toggle_fullscreen, update_ui_scale, virtual_scaling, WindowConfig};
+use ending::{check_endings, track_run_stats, RunGoals, RunStats};
+use events::{
+    BillMissed, CardThrown, CashCollected, DayStarted, EnemyHit, EnemyKilled, GameOver, MonthEnded,
+    PaycheckReceived, PlayerHurt, RentMissed, RentPaid, StartRun, WeekStarted,
+};
+use expenses::{bills_buttons, process_bills, update_bills_panel, Bills};
+use floating::{spawn_floating_numbers, update_floating_text};
+use history::{record_run, RunHistory};
+use hud::{
+    spawn_hud, update_calendar_text, update_card_text, update_cash_text, update_credit_bar,
+    update_magnet_text, update_power_up_icons, update_rent_text,
+};
+use income::{pay_salary, track_performance, Job, PayPeriod};
+use level::{
+    load_level, spawn_level, update_one_way_platforms, CurrentLevel, EnemySpawnPoint, Hazard,
+    LevelData, LEVELS,
+};
+use life_events::{
+    close_life_event, life_event_choices, open_life_event, roll_life_events, LifeEventState,
+    LifeEvents,
+};
+use loot::{drop_loot, Coupons, DropTables};
+use menu::{close_main_menu, main_menu_buttons, open_main_menu};
+use parallax::update_parallax;
+use pause::{
+    close_pause_menu, freeze_physics, open_pause_menu, pause_buttons, resume_physics, toggle_pause,
+};
+use perks::{close_perk_menu, offer_perks, open_perk_menu, perk_buttons, PerkPool, RunPerks};
+use pickups::{buy_magnet, update_pickups, Magnet};
+use powerups::{tick_power_up, DoubleBounty, RapidFire, Shield, SlowEnemies, SLOW_ENEMIES_FACTOR};
+use profile::{save_profile, track_profile, Profile};
+use rand::{seq::IteratorRandom, Rng};
+use run::{
+    close_game_over_menu, despawn_run, game_over_buttons, open_game_over_menu, reset_run,
+    Difficulty, GameRng, RunEntity, RunModifiers, StartRunSet,
+};
+use save::{delete_save, restore_save, save_run, LoadedSave};
+use serde::Deserialize;
+use settings::{settings_buttons, update_settings_text};
+use toast::{show_toasts, spawn_toast_stack, update_toasts, ToastKind, Toasts};
+use ui::{
+    button_presses, button_visuals, menu_navigation, tick_input_grace, ButtonPressed, MenuFocus,
+};
+
+mod achievements;
+mod animation;
+mod audio;
+mod calendar;
+mod camera;
+mod config;
+mod display;
+mod ending;
+mod events;
+mod expenses;
+mod floating;
+mod history;
+mod hud;
+mod income;
+mod level;
+mod life_events;
+mod loot;
+mod menu;
+mod parallax;
+mod pause;
+mod perks;
+mod pickups;
+mod powerups;
+mod profile;
+mod run;
+mod save;
+mod settings;
+mod toast;
+mod ui;
 
 fn main() {
+    let window_config = WindowConfig::load();
+    let calendar_config = CalendarConfig::load();
     let mut app = App::new();
-    app.add_plugins(DefaultPlugins)
-        .insert_resource(CursorPos::default())
-        .insert_resource(CurrentCard(0))
-        .insert_resource(SpawnTimer(Timer::from_seconds(3., TimerMode::Repeating)))
-        .insert_resource(MonthTimer(Timer::from_seconds(75., TimerMode::Repeating)))
-        // .insert_resource(RapierConfiguration {
-        //     gravity: Vec2::new(0., -300.),
-        //     ..Default::default()
-        // })
-        .add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
-        // .add_plugin(RapierDebugRenderPlugin::default())
-        .add_startup_system(setup)
-        .add_systems((
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(window_config.window()),
+        ..default()
+    }))
+    .add_state::<GameState>()
+    .configure_set(GameplaySet.run_if(in_state(GameState::Playing)))
+    .configure_set(StartRunSet.run_if(on_event::<StartRun>()))
+    .insert_resource(CursorPos::default())
+    .insert_resource(CurrentCard(0))
+    .insert_resource(SpawnTimer(Timer::from_seconds(3., TimerMode::Repeating)))
+    .insert_resource(Calendar::new(&calendar_config))
+    .insert_resource(calendar_config)
+    .insert_resource(HurtSettings::load())
+    .init_resource::<PlayerController>()
+    .init_resource::<CurrentLevel>()
+    .insert_resource(Job::load())
+    .insert_resource(Bills::load())
+    .init_resource::<PayPeriod>()
+    .init_resource::<Difficulty>()
+    .init_resource::<LoadedSave>()
+    .init_resource::<RunGoals>()
+    .init_resource::<RunStats>()
+    .init_resource::<MenuFocus>()
+    .init_resource::<Toasts>()
+    .insert_resource(Profile::load())
+    .insert_resource(RunHistory::load())
+    .insert_resource(LifeEvents::load())
+    .init_resource::<LifeEventState>()
+    .insert_resource(GameRng::new(rand::random()))
+    .init_resource::<RunModifiers>()
+    .init_resource::<Magnet>()
+    .insert_resource(DropTables::load())
+    .init_resource::<Coupons>()
+    .insert_resource(PerkPool::load())
+    .init_resource::<RunPerks>()
+    .add_event::<PlayerHurt>()
+    .add_event::<CardThrown>()
+    .add_event::<EnemyHit>()
+    .add_event::<EnemyKilled>()
+    .add_event::<CashCollected>()
+    .add_event::<DayStarted>()
+    .add_event::<WeekStarted>()
+    .add_event::<MonthEnded>()
+    .add_event::<RentPaid>()
+    .add_event::<RentMissed>()
+    .add_event::<PaycheckReceived>()
+    .add_event::<BillMissed>()
+    .add_event::<GameOver>()
+    .add_event::<StartRun>()
+    .add_event::<ButtonPressed>()
+    .init_resource::<AudioSettings>()
+    .init_resource::<SfxQueue>()
+    .init_resource::<CurrentMusic>()
+    // .insert_resource(RapierConfiguration {
+    //     gravity: Vec2::new(0., -300.),
+    //     ..Default::default()
+    // })
+    .add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
+    // .add_plugin(RapierDebugRenderPlugin::default())
+    .add_startup_system(load_animations.in_base_set(StartupSet::PreStartup))
+    .add_startup_systems((spawn_camera, spawn_toast_stack))
+    .add_systems((reset_run, restore_save, setup).chain().in_set(StartRunSet))
+    .add_systems(
+        (
             handle_inputs,
-            update_cursor_pos,
+            update_cursor_pos.after(camera_follow),
             handle_despawn_timers,
-            handle_velocity,
-            tick_month,
+            handle_velocity.after(handle_inputs),
+            tick_calendar,
+            pay_rent.after(tick_calendar),
             handle_proj_collisions,
             spawn_random_enemies,
             enemy_movement,
             handle_health_change,
-            handle_update_money_text,
-            handle_collisions
-                .before(handle_velocity)
-                .before(handle_inputs),
             move_projectiles,
-        ));
+            track_run_stats
+                .after(pay_rent)
+                .after(pay_salary)
+                .after(update_pickups),
+            check_endings.after(track_run_stats),
+        )
+            .in_set(GameplaySet),
+    )
+    .add_systems(
+        (
+            handle_enemy_contact.before(handle_inputs),
+            handle_invulnerability,
+            handle_stun.before(handle_inputs),
+            update_one_way_platforms.before(handle_velocity),
+            camera_follow.after(handle_velocity),
+            update_parallax.after(camera_follow),
+            spawn_floating_numbers
+                .after(handle_proj_collisions)
+                .after(handle_health_change)
+                .after(update_pickups),
+            update_floating_text,
+            update_credit_bar,
+            update_cash_text,
+            update_rent_text,
+            update_calendar_text,
+            update_card_text,
+            update_magnet_text,
+            update_power_up_icons,
+        )
+            .in_set(GameplaySet),
+    )
+    .add_systems(
+        (
+            track_performance,
+            pay_salary.after(tick_calendar),
+            process_bills.after(tick_calendar).after(pay_salary),
+            roll_life_events.after(tick_calendar).after(check_endings),
+            offer_perks.after(check_endings),
+            drop_loot.after(handle_health_change),
+            update_pickups.after(drop_loot),
+            buy_magnet,
+            tick_power_up::<DoubleBounty>,
+            tick_power_up::<RapidFire>,
+            tick_power_up::<Shield>,
+            tick_power_up::<SlowEnemies>,
+        )
+            .in_set(GameplaySet),
+    )
+    .add_systems(
+        (
+            player_animation_state.after(handle_inputs),
+            enemy_animation_state.after(handle_proj_collisions),
+            animate_sprites
+                .after(player_animation_state)
+                .after(enemy_animation_state),
+        )
+            .in_set(GameplaySet),
+    )
+    .add_systems((
+        queue_sfx,
+        play_sfx.after(queue_sfx),
+        update_music,
+        toggle_mute,
+    ))
+    .add_systems(
+        (save_run, despawn_run, open_main_menu)
+            .chain()
+            .in_schedule(OnEnter(GameState::MainMenu)),
+    )
+    .add_system(save_profile.in_schedule(OnEnter(GameState::MainMenu)))
+    .add_system(close_main_menu.in_schedule(OnExit(GameState::MainMenu)))
+    .add_systems((open_pause_menu, freeze_physics).in_schedule(OnEnter(GameState::Paused)))
+    .add_systems((close_pause_menu, resume_physics).in_schedule(OnExit(GameState::Paused)))
+    .add_systems(
+        (
+            open_game_over_menu,
+            freeze_physics,
+            delete_save,
+            record_run,
+            save_profile,
+        )
+            .in_schedule(OnEnter(GameState::GameOver)),
+    )
+    .add_systems((close_game_over_menu, resume_physics).in_schedule(OnExit(GameState::GameOver)))
+    .add_systems((open_life_event, freeze_physics).in_schedule(OnEnter(GameState::LifeEvent)))
+    .add_systems((close_life_event, resume_physics).in_schedule(OnExit(GameState::LifeEvent)))
+    .add_systems((open_perk_menu, freeze_physics).in_schedule(OnEnter(GameState::PerkSelect)))
+    .add_systems((close_perk_menu, resume_physics).in_schedule(OnExit(GameState::PerkSelect)))
+    .add_systems((
+        toggle_pause,
+        pause_buttons,
+        settings_buttons,
+        bills_buttons,
+        update_bills_panel,
+        update_settings_text,
+        tick_input_grace
+            .before(menu_navigation)
+            .before(button_presses),
+        menu_navigation,
+        button_presses,
+        button_visuals.after(menu_navigation),
+        main_menu_buttons.run_if(in_state(GameState::MainMenu)),
+        game_over_buttons.run_if(in_state(GameState::GameOver)),
+        life_event_choices.run_if(in_state(GameState::LifeEvent)),
+        perk_buttons.run_if(in_state(GameState::PerkSelect)),
+    ))
+    .add_systems((
+        track_profile,
+        check_achievements.after(track_profile),
+        show_toasts,
+        update_toasts,
+        update_ui_scale,
+        toggle_fullscreen,
+    ));
 
     app.run();
 }
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameState {
+    #[default]
+    MainMenu,
+    Playing,
+    Paused,
+    /// A life event is waiting on the player's choice.
+    LifeEvent,
+    /// Picking a perk after a month ends.
+    PerkSelect,
+    GameOver,
+}
+
+/// Everything that should freeze while the game isn't being played.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplaySet;
+
 const GRAVITY: f32 = -200.0;
-const PLATFORM_SIZE: Vec2 = Vec2::new(20000.0, 50.0);
-const SMALL_PLATFORM_SIZE: Vec2 = Vec2::new(300.0, 50.0);
+const PLAYER_HALF_SIZE: Vec2 = Vec2::new(25.0, 50.0);
 const MAX_PLAYER_VEL: f32 = 500.;
+
+// The player's health is their credit score, going bankrupt at the floor
+pub const CREDIT_FLOOR: f32 = 300.;
+pub const CREDIT_MAX: f32 = 850.;
+const CREDIT_START: f32 = 650.;
+/// Credit score gained for paying rent on time, and lost for missing it.
+pub const RENT_CREDIT: f32 = 40.;
 #[derive(Component)]
 pub struct Player;
 
@@ -60,13 +330,132 @@ pub struct MaxHealth(pub f32);
 
 #[derive(Component)]
 pub struct CurrentHealth(pub f32);
-#[derive(Component)]
-pub struct CollidedThisFrame(pub Timer);
 
 #[derive(Component)]
 
 pub struct Gravity(pub Vec2);
 
+// CONTROLLER STUFF
+#[derive(Resource)]
+pub struct PlayerController {
+    pub max_speed: f32,
+    pub acceleration: f32,
+    pub deceleration: f32,
+    pub jump_velocity: f32,
+    /// Multiplier applied to upward velocity when jump is released early.
+    pub jump_cut: f32,
+    pub coyote_secs: f32,
+    pub jump_buffer_secs: f32,
+    pub fast_fall: f32,
+    /// Shortest gap between two thrown cards, before perks.
+    pub throw_cooldown_secs: f32,
+}
+
+impl Default for PlayerController {
+    fn default() -> Self {
+        Self {
+            max_speed: 300.,
+            acceleration: 2000.,
+            deceleration: 2500.,
+            jump_velocity: 250.,
+            jump_cut: 0.5,
+            coyote_secs: 0.1,
+            jump_buffer_secs: 0.12,
+            fast_fall: 400.,
+            throw_cooldown_secs: 0.25,
+        }
+    }
+}
+
+/// Coyote time counts up since the player last stood on ground,
+/// the buffer counts up since jump was last pressed.
+#[derive(Component)]
+pub struct JumpState {
+    pub coyote: Timer,
+    pub buffer: Timer,
+    /// Jumps used since leaving the ground.
+    pub air_jumps: u32,
+}
+
+impl JumpState {
+    pub fn new(controller: &PlayerController) -> Self {
+        Self {
+            coyote: finished_timer(controller.coyote_secs),
+            buffer: finished_timer(controller.jump_buffer_secs),
+            air_jumps: 0,
+        }
+    }
+}
+
+/// Counts up since the last card was thrown.
+#[derive(Component)]
+pub struct ThrowCooldown(pub Timer);
+
+impl ThrowCooldown {
+    pub fn new(controller: &PlayerController) -> Self {
+        Self(finished_timer(controller.throw_cooldown_secs))
+    }
+}
+
+fn finished_timer(secs: f32) -> Timer {
+    let mut timer = Timer::from_seconds(secs, TimerMode::Once);
+    timer.tick(timer.duration());
+    timer
+}
+
+fn move_towards(current: f32, target: f32, max_delta: f32) -> f32 {
+    if (target - current).abs() <= max_delta {
+        target
+    } else {
+        current + (target - current).signum() * max_delta
+    }
+}
+
+// HURT STUFF
+const HURT_CONFIG: &str = "config/hurt.ron";
+
+#[derive(Resource, Deserialize)]
+#[serde(default)]
+pub struct HurtSettings {
+    pub invulnerable_secs: f32,
+    pub blink_secs: f32,
+    /// Sideways and upwards push away from what hit the player.
+    pub knockback: (f32, f32),
+    pub stun_secs: f32,
+}
+
+impl Default for HurtSettings {
+    fn default() -> Self {
+        Self {
+            invulnerable_secs: 1.0,
+            blink_secs: 0.1,
+            knockback: (250., 150.),
+            stun_secs: 0.25,
+        }
+    }
+}
+
+impl HurtSettings {
+    pub fn load() -> HurtSettings {
+        load_config(HURT_CONFIG)
+    }
+}
+
+/// I-frames after being hit; the sprite blinks until the timer runs out.
+#[derive(Component)]
+pub struct Invulnerable {
+    pub timer: Timer,
+    pub blink: Timer,
+}
+
+/// Horizontal input is ignored while this is present so knockback isn't cancelled.
+#[derive(Component)]
+pub struct Stunned(pub Timer);
+
+/// Credit score lost when the player touches this entity.
+#[derive(Component)]
+pub struct ContactDamage(pub f32);
+
 #[derive(Component, Default)]
 pub struct Velocity(pub Vec2);
 
@@ -74,6 +463,13 @@ pub struct Velocity(pub Vec2);
 #[derive(Component)]
 pub struct Enemy;
 
+/// Which drop table an enemy rolls on when it dies.
+#[derive(Component)]
+pub struct EnemyKind(pub &'static str);
+
+/// Every kind of enemy that gets spawned.
+pub const ENEMY_KINDS: [&str; 1] = ["bill"];
+
 const ENEMY_SPEED: f32 = 200.0;
 const ENEMY_X_RANGE: f32 = 100.0;
 const ENEMY_JUMP_FORCE: f32 = 100.0;
@@ -84,43 +480,68 @@ pub struct EnemyDirection(f32, Timer);
 #[derive(Component)]
 pub struct JumpTimer(Timer);
 
-#[derive(Component)]
-pub struct HPBar;
 #[derive(Component)]
 
 pub struct MoneyText(pub i32, pub i32);
-#[derive(Component)]
 
-pub struct DayText;
+/// The camera outlives runs so menus have something to render with.
+pub fn spawn_camera(mut commands: Commands) {
+    let mut camera = Camera2dBundle::default();
+    camera.projection.scaling_mode = virtual_scaling();
+    commands.spawn((camera, MainCamera, CameraFollow::default()));
+}
 
-pub fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    // Camera
-    commands.spawn(Camera2dBundle::default());
-    // Background
-    commands.spawn(SpriteBundle {
-        sprite: Sprite {
-            // color: Color::rgb(0.25, 0.25, 0.75),
-            custom_size: Some(Vec2::new(1290.0, 750.0)),
-            ..default()
-        },
-        texture: asset_server.load("city-background.png"),
-        transform: Transform {
-            translation: Vec3::new(0.0, 0.0, 0.0), // Ensure the background is behind all entities
-            ..default()
-        },
-        ..default()
+pub fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    controller: Res<PlayerController>,
+    current_level: Res<CurrentLevel>,
+    animations: Res<AnimationLibrary>,
+    mut camera: Query<&mut Transform, With<MainCamera>>,
+    difficulty: Res<Difficulty>,
+    mut loaded_save: ResMut<LoadedSave>,
+    modifiers: Res<RunModifiers>,
+) {
+    // Continue picks up where the save left off, `restore_save` has done the resources
+    let (cash, rent, credit) = match loaded_save.0.take() {
+        Some(save) => (save.cash, save.rent, save.credit),
+        None => (difficulty.starting_cash(), 2800, CREDIT_START),
+    };
+
+    // Level. The index can come from a save, so it isn't trusted
+    let level = LEVELS
+        .get(current_level.0)
+        .ok_or_else(|| format!("no level {}", current_level.0))
+        .and_then(|(_, path)| load_level(path))
+        .unwrap_or_else(|e| {
+            println!("{e}, using a bare floor instead");
+            LevelData::fallback()
+        });
+    spawn_level(&mut commands, &asset_server, &level);
+    let ((min_x, min_y), (max_x, max_y)) = level.bounds;
+    commands.insert_resource(LevelBounds {
+        min: Vec2::new(min_x, min_y),
+        max: Vec2::new(max_x, max_y),
     });
+
+    // Camera
+    for mut cam_t in camera.iter_mut() {
+        cam_t.translation.x = level.player_start.0;
+        cam_t.translation.y = level.player_start.1;
+    }
     // Rectangle
     commands
-        .spawn(SpriteBundle {
-            sprite: Sprite {
-                // color: Color::rgb(0.25, 0.25, 0.75),
+        .spawn(SpriteSheetBundle {
+            sprite: TextureAtlasSprite {
                 custom_size: Some(Vec2::new(100.0, 100.0)),
                 ..default()
             },
-            texture: asset_server.load("chester.png"),
-
-            transform: Transform::from_translation(Vec3::new(-50., -300., 1.)),
+            texture_atlas: animations.atlas("chester"),
+            transform: Transform::from_translation(Vec3::new(
+                level.player_start.0,
+                level.player_start.1,
+                1.,
+            )),
             ..default()
         })
         .insert(KinematicCharacterController {
@@ -130,161 +551,28 @@ pub fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             ..default()
         })
         // .insert(RigidBody::Dynamic)
-        .insert(Collider::cuboid(25.0, 50.0))
+        .insert(Collider::cuboid(PLAYER_HALF_SIZE.x, PLAYER_HALF_SIZE.y))
         .insert(Velocity::default())
         .insert(Gravity(Vec2::new(0., GRAVITY)))
+        .insert(JumpState::new(&controller))
+        .insert(ThrowCooldown::new(&controller))
+        .insert(Animator::new("chester", AnimationClip::Idle))
         .insert(Player)
-        .insert(MaxHealth(100.0))
-        .insert(CurrentHealth(100.0));
-
-    // PLAYER HP BAR
-    commands
-        .spawn(SpriteBundle {
-            sprite: Sprite {
-                color: Color::rgb(0.9, 0.1, 0.1),
-                custom_size: Some(Vec2::new(200.0, 10.0)),
-                anchor: Anchor::CenterLeft,
-                ..default()
-            },
-            transform: Transform::from_translation(Vec3::new(-490., 340., 1.)),
-            ..default()
-        })
-        .insert(HPBar);
-
-    commands.spawn((
-        // Create a TextBundle that has a Text with a single section.
-        TextBundle::from_section(
-            // Accepts a `String` or any type that converts into a `String`, such as `&str`
-            "Credit Score",
-            TextStyle {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                font_size: 30.0,
-                color: Color::WHITE,
-            },
-        ) // Set the alignment of the Text
-        .with_text_alignment(TextAlignment::Left)
-        // Set the style of the TextBundle itself.
-        .with_style(Style {
-            position_type: PositionType::Absolute,
-            position: UiRect {
-                top: Val::Px(5.0),
-                left: Val::Px(5.0),
-                ..default()
-            },
-            ..default()
-        }),
-    ));
-    commands.spawn((
-        // Create a TextBundle that has a Text with a single section.
-        TextBundle::from_section(
-            // Accepts a `String` or any type that converts into a `String`, such as `&str`
-            "Day 1",
-            TextStyle {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                font_size: 40.0,
-                color: Color::ORANGE_RED,
-            },
-        ) // Set the alignment of the Text
-        .with_text_alignment(TextAlignment::Left)
-        // Set the style of the TextBundle itself.
-        .with_style(Style {
-            position_type: PositionType::Absolute,
-            position: UiRect {
-                top: Val::Px(5.0),
-                left: Val::Px(600.0),
-                ..default()
-            },
-            ..default()
-        }),
-        DayText,
-    ));
+        .insert(MaxHealth(CREDIT_MAX + modifiers.max_credit))
+        .insert(CurrentHealth(credit))
+        .insert(RunEntity);
 
-    commands.spawn((
-        // Create a TextBundle that has a Text with a single section.
-        TextBundle::from_section(
-            // Accepts a `String` or any type that converts into a `String`, such as `&str`
-            "$0",
-            TextStyle {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                font_size: 20.0,
-                color: Color::GOLD,
-            },
-        ) // Set the alignment of the Text
-        .with_text_alignment(TextAlignment::Left)
-        // Set the style of the TextBundle itself.
-        .with_style(Style {
-            position_type: PositionType::Absolute,
-            position: UiRect {
-                top: Val::Px(5.0),
-                right: Val::Px(10.0),
-                ..default()
-            },
-            ..default()
-        }),
-        MoneyText(0, 2800),
-    ));
+    spawn_hud(&mut commands, &asset_server, cash, rent);
 
-    // Rectangle - Enemy
-    commands
-        .spawn(SpriteBundle {
-            sprite: Sprite {
-                custom_size: Some(Vec2::new(100.0, 100.0)),
-                ..default()
-            },
-            texture: asset_server.load("bill-asset.png"),
-            transform: Transform::from_translation(Vec3::new(0.0, -305., 1.)),
-            ..default()
-        })
-        // .insert(RigidBody::Dynamic)
-        .insert(Collider::cuboid(50.0, 50.0))
-        .insert(Velocity::default())
-        .insert(Enemy)
-        .insert(MaxHealth(50.0))
-        .insert(CurrentHealth(50.0))
-        .insert(Sensor)
-        .insert(EnemyDirection(
-            1.0,
-            Timer::from_seconds(2., TimerMode::Repeating),
-        ))
-        .insert(JumpTimer(Timer::from_seconds(10.0, TimerMode::Repeating)));
-
-    // Platform
-    commands
-        .spawn(Collider::cuboid(PLATFORM_SIZE.x / 2., PLATFORM_SIZE.y / 2.))
-        .insert(TransformBundle::from_transform(
-            Transform::from_translation(Vec3::new(-200., -380., 0.)),
-        ))
-        .insert(Sensor);
-    // commands
-    //     .spawn(SpriteBundle {
-    //         sprite: Sprite {
-    //             color: Color::rgb(0.75, 0.25, 0.25),
-    //             custom_size: Some(SMALL_PLATFORM_SIZE),
-    //             ..default()
-    //         },
-    //         transform: Transform::from_translation(Vec3::new(-200., 150., 0.)),
-    //         ..default()
-    //     })
-    //     .insert(Collider::cuboid(
-    //         SMALL_PLATFORM_SIZE.x / 2.,
-    //         SMALL_PLATFORM_SIZE.y / 2.,
-    //     ))
-    //     .insert(Sensor);
-    // commands
-    //     .spawn(SpriteBundle {
-    //         sprite: Sprite {
-    //             color: Color::rgb(0.75, 0.25, 0.25),
-    //             custom_size: Some(SMALL_PLATFORM_SIZE),
-    //             ..default()
-    //         },
-    //         transform: Transform::from_translation(Vec3::new(200., 200., 0.)),
-    //         ..default()
-    //     })
-    //     .insert(Collider::cuboid(
-    //         SMALL_PLATFORM_SIZE.x / 2.,
-    //         SMALL_PLATFORM_SIZE.y / 2.,
-    //     ))
-    //     .insert(Sensor);
+    // Enemies
+    for spawn in level.enemy_spawns.iter().filter(|s| s.initial) {
+        spawn_enemy(
+            &mut commands,
+            &animations,
+            Vec3::new(spawn.position.0, spawn.position.1, 1.),
+            2.,
+        );
+    }
 }
 
 #[derive(Resource)]
@@ -292,7 +580,16 @@ pub struct CurrentCard(pub i32);
 
 pub fn handle_inputs(
     mut player_query: Query<
-        (Entity, &mut Velocity, &mut Gravity, &mut Sprite, &Transform),
+        (
+            &mut Velocity,
+            &mut JumpState,
+            &mut ThrowCooldown,
+            &mut TextureAtlasSprite,
+            &Transform,
+            Option<&KinematicCharacterControllerOutput>,
+            Option<&Stunned>,
+            Option<&mut RapidFire>,
+        ),
         With<Player>,
     >,
     time: Res<Time>,
@@ -303,32 +600,28 @@ pub fn handle_inputs(
     asset_server: Res<AssetServer>,
     mut money: Query<&mut MoneyText>,
     mut card: ResMut<CurrentCard>,
+    controller: Res<PlayerController>,
+    mut throw_events: EventWriter<CardThrown>,
+    modifiers: Res<RunModifiers>,
 ) {
-    let (player_e, mut vel, mut grav, mut sprite, transform) = player_query.single_mut();
-    let mut d = Vec2::ZERO;
-    let speed = 200.;
-    let s = speed * time.delta_seconds();
-
-    if key_input.pressed(KeyCode::A) {
-        d.x -= 1.;
-        if vel.0.x > 0. {
-            vel.0.x = 0.;
-        }
+    let (mut vel, mut jump, mut throw_cooldown, mut sprite, transform, output, stunned, rapid_fire) =
+        player_query.single_mut();
+    let dt = time.delta_seconds();
+    let mut dir = 0.;
+
+    if stunned.is_none() && key_input.pressed(KeyCode::A) {
+        dir -= 1.;
         sprite.flip_x = false;
     }
-    if key_input.pressed(KeyCode::D) {
-        d.x += 1.;
-        if vel.0.x < 0. {
-            vel.0.x = 0.;
-        }
+    if stunned.is_none() && key_input.pressed(KeyCode::D) {
+        dir += 1.;
         sprite.flip_x = true;
     }
     if key_input.pressed(KeyCode::W) {
         println!("W");
-        // d.y += 1.;
     }
     if key_input.pressed(KeyCode::S) {
-        d.y -= 1.;
+        vel.0.y -= controller.fast_fall * dt;
     }
 
     if key_input.pressed(KeyCode::Q) {
@@ -340,10 +633,20 @@ pub fn handle_inputs(
         }
     }
 
-    if !key_input.pressed(KeyCode::A) && !key_input.pressed(KeyCode::D) {
-        vel.0.x = 0.;
-    }
-    if mouse_input.just_pressed(MouseButton::Left) {
+    throw_cooldown
+        .0
+        .set_duration(std::time::Duration::from_secs_f32(
+            controller.throw_cooldown_secs / modifiers.throw_rate,
+        ));
+    throw_cooldown.0.tick(time.delta());
+    let throw = mouse_input.just_pressed(MouseButton::Left) && throw_cooldown.0.finished();
+    // Rapid fire keeps throwing while the button is held
+    let rapid_throw = rapid_fire.map_or(false, |mut rapid_fire| {
+        rapid_fire.cooldown.tick(time.delta());
+        mouse_input.pressed(MouseButton::Left) && rapid_fire.cooldown.just_finished()
+    });
+    if throw || rapid_throw {
+        throw_cooldown.0.reset();
         // Create a small entity used as a projectile
         let direction =
             (cur.world_coords.truncate() - transform.translation.truncate()).normalize();
@@ -371,21 +674,48 @@ pub fn handle_inputs(
             Collider::cuboid(5.0, 5.0),
             Sensor,
             Projectile { direction, damage },
+            RunEntity,
             Velocity(direction * 300.0), // Set the projectile direction and speed
         ));
+        throw_events.send(CardThrown);
     }
-    if key_input.just_pressed(KeyCode::Space) {
-        vel.0.y = 150.;
+    // Knockback keeps its momentum until the stun wears off
+    if stunned.is_none() {
+        let target = dir * controller.max_speed;
+        let rate = if dir != 0. && (vel.0.x == 0. || dir == vel.0.x.signum()) {
+            controller.acceleration
+        } else {
+            controller.deceleration
+        };
+        vel.0.x = move_towards(vel.0.x, target, rate * dt);
     }
 
-    if d.x != 0. || d.y != 0. {
-        d = d.normalize() * s;
+    let grounded = output.map_or(false, |o| o.grounded);
+    if grounded {
+        jump.coyote.reset();
+        jump.air_jumps = 0;
+    } else {
+        jump.coyote.tick(time.delta());
     }
-
-    if vel.0.y > 0. {
-        grav.0 = Vec2::new(0., GRAVITY);
+    jump.buffer.tick(time.delta());
+    if key_input.just_pressed(KeyCode::Space) {
+        jump.buffer.reset();
+    }
+    if !jump.buffer.finished() && !jump.coyote.finished() {
+        vel.0.y = controller.jump_velocity;
+        // Consume both so a held or buffered press can't jump twice
+        let (coyote, buffer) = (jump.coyote.duration(), jump.buffer.duration());
+        jump.coyote.tick(coyote);
+        jump.buffer.tick(buffer);
+    } else if key_input.just_pressed(KeyCode::Space) && jump.air_jumps < modifiers.extra_jumps {
+        vel.0.y = controller.jump_velocity;
+        jump.air_jumps += 1;
+        let buffer = jump.buffer.duration();
+        jump.buffer.tick(buffer);
+    }
+    if key_input.just_released(KeyCode::Space) && vel.0.y > 0. {
+        vel.0.y *= controller.jump_cut;
     }
-    vel.0 += d;
 
     if vel.0.x >= MAX_PLAYER_VEL {
         vel.0.x = MAX_PLAYER_VEL;
@@ -396,39 +726,31 @@ pub fn handle_inputs(
     if vel.0.y >= MAX_PLAYER_VEL {
         vel.0.y = MAX_PLAYER_VEL;
     }
-    // println!("{s:?} ||| {d:?}, {:?}", vel.0);
-
-    // if d.x != 0. || d.y != 0. {
-    //     player_kcc.translation = Some(vel.0);
-    // }
 }
 
 pub fn handle_velocity(
     mut player_query: Query<
         (
-            Entity,
             &mut KinematicCharacterController,
             &mut Velocity,
-            &mut Gravity,
-            Option<&mut CollidedThisFrame>,
+            &Gravity,
+            Option<&KinematicCharacterControllerOutput>,
         ),
-        (With<Player>,),
+        With<Player>,
     >,
     time: Res<Time>,
-    mut commands: Commands,
 ) {
-    for (e, mut transform, mut vel, mut grav, mut collided) in player_query.iter_mut() {
-        if let Some(mut collider) = collided {
-            if collider.0.percent() == 0. {
-                grav.0 = Vec2::ZERO;
-                collider.0.tick(time.delta());
-                // continue;
+    for (mut transform, mut vel, grav, output) in player_query.iter_mut() {
+        if let Some(output) = output {
+            // Stop on ground and ceilings instead of building up speed against them
+            if output.grounded && vel.0.y < 0. {
+                vel.0.y = 0.;
             }
-            collider.0.tick(time.delta());
-            if collider.0.finished() {
-                grav.0 = Vec2::new(0., GRAVITY);
-
-                commands.entity(e).remove::<CollidedThisFrame>();
+            if vel.0.y > 0.
+                && output.desired_translation.y > 0.
+                && output.effective_translation.y <= 0.
+            {
+                vel.0.y = 0.;
             }
         }
         let mut new_vel = vel.0 + grav.0 * time.delta_seconds();
@@ -436,36 +758,97 @@ pub fn handle_velocity(
             new_vel.y = grav.0.y * 2.;
         }
         vel.0 = new_vel;
-        // println!("MOVING {:?} {:?}", new_vel, grav.0);
         transform.translation = Some(new_vel * time.delta_seconds());
     }
 }
 
-pub fn handle_collisions(
+pub fn handle_enemy_contact(
     mut player: Query<
-        (Entity, &mut Velocity, &MaxHealth, &mut CurrentHealth),
-        (With<Player>, Without<CollidedThisFrame>),
+        (
+            Entity,
+            &Transform,
+            &mut Velocity,
+            &mut CurrentHealth,
+            Option<&Shield>,
+        ),
+        (With<Player>, Without<Invulnerable>),
     >,
-    enemies: Query<Entity, With<Enemy>>,
-    proj: Query<Entity, With<Projectile>>,
+    enemies: Query<(&Transform, &ContactDamage), Or<(With<Enemy>, With<Hazard>)>>,
+    settings: Res<HurtSettings>,
+    mut hurt_events: EventWriter<PlayerHurt>,
     mut commands: Commands,
-    context: ResMut<RapierContext>,
+    context: Res<RapierContext>,
 ) {
-    let Ok((player, mut vel, max_hp, mut curr_hp)) = player.get_single_mut() else {
+    let Ok((player, t, mut vel, mut curr_hp, shield)) = player.get_single_mut() else {
         return;
     };
 
-    let hits_this_frame = context.intersection_pairs().filter(|c| {
-        ((c.0 == player) || (c.1 == player)) && proj.get(c.0).is_err() && proj.get(c.1).is_err()
-    });
-    for (e1, e2, _) in hits_this_frame {
-        if (e1 == player && enemies.get(e2).is_ok()) || (e2 == player && enemies.get(e1).is_ok()) {
-            curr_hp.0 -= 10.;
-            println!("HIT ENEMY {:?}", curr_hp.0);
+    for (e1, e2, _) in context.intersection_pairs() {
+        let other = if e1 == player {
+            e2
+        } else if e2 == player {
+            e1
+        } else {
+            continue;
+        };
+        let Ok((enemy_t, damage)) = enemies.get(other) else {
+            continue;
+        };
+
+        // A shield takes the hit instead of the credit score
+        if shield.is_some() {
+            commands.entity(player).remove::<Shield>();
+        } else {
+            curr_hp.0 -= damage.0;
+            hurt_events.send(PlayerHurt { damage: damage.0 });
+        }
+
+        let away = if t.translation.x < enemy_t.translation.x {
+            -1.
+        } else {
+            1.
+        };
+        vel.0 = Vec2::new(away * settings.knockback.0, settings.knockback.1);
+        commands.entity(player).insert((
+            Invulnerable {
+                timer: Timer::from_seconds(settings.invulnerable_secs, TimerMode::Once),
+                blink: Timer::from_seconds(settings.blink_secs, TimerMode::Repeating),
+            },
+            Stunned(Timer::from_seconds(settings.stun_secs, TimerMode::Once)),
+        ));
+        // one hit per contact, the i-frames cover the rest
+        break;
+    }
+}
+
+pub fn handle_invulnerability(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Invulnerable, &mut TextureAtlasSprite)>,
+) {
+    for (entity, mut invulnerable, mut sprite) in query.iter_mut() {
+        invulnerable.timer.tick(time.delta());
+        invulnerable.blink.tick(time.delta());
+        if invulnerable.timer.finished() {
+            sprite.color.set_a(1.);
+            commands.entity(entity).remove::<Invulnerable>();
+        } else if invulnerable.blink.just_finished() {
+            let alpha = if sprite.color.a() < 1. { 1. } else { 0.3 };
+            sprite.color.set_a(alpha);
+        }
+    }
+}
+
+pub fn handle_stun(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Stunned)>,
+) {
+    for (entity, mut stunned) in query.iter_mut() {
+        stunned.0.tick(time.delta());
+        if stunned.0.finished() {
+            commands.entity(entity).remove::<Stunned>();
         }
-        commands
-            .entity(player)
-            .insert(CollidedThisFrame(Timer::from_seconds(0.1, TimerMode::Once)));
     }
 }
 
@@ -475,6 +858,7 @@ pub fn handle_proj_collisions(
     mut commands: Commands,
     context: ResMut<RapierContext>,
     card: Res<CurrentCard>,
+    mut hit_events: EventWriter<EnemyHit>,
 ) {
     for (e1, e2, _) in context.intersection_pairs() {
         for (enemy, mut curr_hp) in enemies.iter_mut() {
@@ -485,7 +869,9 @@ pub fn handle_proj_collisions(
                     commands.entity(e2).despawn();
                 };
 
-                curr_hp.0 -= if card.0 == 0 { 10. } else { 15. };
+                let damage = if card.0 == 0 { 10. } else { 15. };
+                curr_hp.0 -= damage;
+                hit_events.send(EnemyHit { enemy, damage });
 
                 println!("HIT ENEMY {:?}", curr_hp.0);
             }
@@ -496,9 +882,15 @@ pub fn handle_proj_collisions(
 pub fn enemy_movement(
     time: Res<Time>,
     mut query: Query<(&mut Transform, &mut EnemyDirection, &mut JumpTimer), With<Enemy>>,
+    slowed: Query<(), (With<Player>, With<SlowEnemies>)>,
 ) {
+    let speed = if slowed.is_empty() {
+        ENEMY_SPEED
+    } else {
+        ENEMY_SPEED * SLOW_ENEMIES_FACTOR
+    };
     for (mut transform, mut direction, mut jump_timer) in query.iter_mut() {
-        let delta_move = ENEMY_SPEED * direction.0 * time.delta_seconds();
+        let delta_move = speed * direction.0 * time.delta_seconds();
         transform.translation.x += delta_move;
 
         direction.1.tick(time.delta());
@@ -536,65 +928,41 @@ pub fn handle_health_change(
             &Transform,
             &MaxHealth,
             &CurrentHealth,
-            Option<&Player>,
+            Option<&EnemyKind>,
         ),
         Changed<CurrentHealth>,
     >,
-    mut hp_bar: Query<&mut Sprite, With<HPBar>>,
-    mut money: Query<&mut MoneyText>,
     mut commands: Commands,
-    card: Res<CurrentCard>,
-    asset_server: Res<AssetServer>,
+    animations: Res<AnimationLibrary>,
+    mut kill_events: EventWriter<EnemyKilled>,
+    double_bounty: Query<(), (With<Player>, With<DoubleBounty>)>,
 ) {
-    for (entity, t, max_hp, curr_hp, player_option) in query.iter() {
+    let cash_multiplier = if double_bounty.is_empty() { 1. } else { 2. };
+    for (entity, t, max_hp, curr_hp, kind) in query.iter() {
         println!("HP: {}/{}", curr_hp.0, max_hp.0);
-        if player_option.is_some() {
-            for mut sprite in hp_bar.iter_mut() {
-                sprite.custom_size = Some(Vec2::new(200.0 * (curr_hp.0 / max_hp.0), 10.));
-            }
-        } else {
-            if curr_hp.0 <= 0. {
-                println!("KILL!");
-                commands.entity(entity).despawn_recursive();
-                money.single_mut().0 += if card.0 == 0 { 100 } else { 150 };
-
-                commands
-                    .spawn((
-                        DespawnTimer(Timer::from_seconds(1., TimerMode::Once)),
-                        // Create a TextBundle that has a Text with a single section.
-                        TextBundle::from_section(
-                            // Accepts a `String` or any type that converts into a `String`, such as `&str`
-                            format!("+${}", if card.0 == 0 { 100 } else { 150 }),
-                            TextStyle {
-                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                                font_size: 40.0,
-                                color: Color::GREEN,
-                            },
-                        ) // Set the alignment of the Text
-                        .with_text_alignment(TextAlignment::Left)
-                        // Set the style of the TextBundle itself.
-                        .with_style(Style {
-                            position_type: PositionType::Absolute,
-                            position: UiRect {
-                                top: Val::Px(200.0),
-                                left: Val::Px(300.0),
-                                ..default()
-                            },
-                            ..default()
-                        }),
-                    ))
-                    .insert(t.clone());
-            }
+        let Some(kind) = kind else {
+            continue;
+        };
+        if curr_hp.0 <= 0. {
+            println!("KILL!");
+            // Stop it from moving and hurting while the death clip plays
+            let die = animations.clip("bill", AnimationClip::Die);
+            commands
+                .entity(entity)
+                .remove::<(Enemy, Collider, Sensor, ContactDamage)>()
+                .insert((
+                    Dying,
+                    DespawnTimer(Timer::from_seconds(die.duration(), TimerMode::Once)),
+                ));
+            kill_events.send(EnemyKilled {
+                position: t.translation,
+                kind: kind.0,
+                cash_multiplier,
+            });
         }
     }
 }
 
-pub fn handle_update_money_text(mut query: Query<(&MoneyText, &mut Text), Changed<MoneyText>>) {
-    for (money, mut text) in query.iter_mut() {
-        text.sections[0].value = format!("RENT: ${} Cash: ${}", money.1, money.0);
-    }
-}
-
 #[derive(Default, Resource, Debug)]
 pub struct CursorPos {
     pub world_coords: Vec3,
@@ -604,22 +972,22 @@ pub struct CursorPos {
 
 pub fn update_cursor_pos(
     windows: Query<&Window, With<PrimaryWindow>>,
-    camera_q: Query<(&Transform, &Camera)>,
-    mut cursor_moved_events: EventReader<CursorMoved>,
+    camera_q: Query<(&Transform, &Camera), With<MainCamera>>,
     mut cursor_pos: ResMut<CursorPos>,
 ) {
-    for cursor_moved in cursor_moved_events.iter() {
-        // To get the mouse's world position, we have to transform its window position by
-        // any transforms on the camera. This is done by projecting the cursor position into
-        // camera space (world space).
-        for (cam_t, cam) in camera_q.iter() {
-            *cursor_pos = CursorPos {
-                world_coords: cursor_pos_in_world(&windows, cursor_moved.position, cam_t, cam),
-                ui_coords: cursor_pos_in_ui(&windows, cursor_moved.position, cam),
-                screen_coords: cursor_moved.position.extend(0.),
-            };
-        }
-        // println!("cur {:?}", cursor_pos.screen_coords);
+    // Recomputed every frame since the world under a still cursor moves with the camera
+    let Some(position) = windows.get_single().ok().and_then(|w| w.cursor_position()) else {
+        return;
+    };
+    // To get the mouse's world position, we have to transform its window position by
+    // any transforms on the camera. This is done by projecting the cursor position into
+    // camera space (world space).
+    for (cam_t, cam) in camera_q.iter() {
+        *cursor_pos = CursorPos {
+            world_coords: cursor_pos_in_world(&windows, position, cam_t, cam),
+            ui_coords: cursor_pos_in_ui(&windows, position, cam),
+            screen_coords: position.extend(0.),
+        };
     }
 }
 
@@ -658,133 +1026,105 @@ pub fn cursor_pos_in_ui(
 
 #[derive(Resource)]
 pub struct SpawnTimer(Timer);
-#[derive(Resource)]
-pub struct MonthTimer(Timer);
 
 pub fn spawn_random_enemies(
     time: Res<Time>,
     mut spawn_timer: ResMut<SpawnTimer>,
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    animations: Res<AnimationLibrary>,
+    spawn_points: Query<(&Transform, &EnemySpawnPoint)>,
+    mut rng: ResMut<GameRng>,
+    modifiers: Res<RunModifiers>,
 ) {
-    spawn_timer.0.tick(time.delta());
+    spawn_timer
+        .0
+        .tick(time.delta().mul_f32(modifiers.spawn_rate));
     if spawn_timer.0.finished() {
-        // Spawn an enemy
-        let mut rng = rand::thread_rng();
-        let pos = Vec3::new(
-            rng.gen_range(-300.0..300.0),
-            rng.gen_range(-200.0..300.0),
-            1.,
+        // Spawn an enemy at one of the level's spawn points
+        let rng = &mut **rng;
+        let Some((t, spawn)) = spawn_points.iter().choose(rng) else {
+            return;
+        };
+        let offset = Vec3::new(
+            rng.gen_range(-1.0..=1.0) * spawn.spread.x,
+            rng.gen_range(-1.0..=1.0) * spawn.spread.y,
+            0.,
+        );
+        spawn_enemy(
+            &mut commands,
+            &animations,
+            t.translation + offset,
+            rng.gen_range(0.3_f32..1.2_f32),
         );
-        commands
-            .spawn(SpriteBundle {
-                sprite: Sprite {
-                    custom_size: Some(Vec2::new(100.0, 100.0)),
-                    ..default()
-                },
-                texture: asset_server.load("bill-asset.png"),
-                transform: Transform::from_translation(pos),
-                ..default()
-            })
-            // .insert(RigidBody::Dynamic)
-            .insert(Collider::cuboid(50.0, 50.0))
-            // .insert(Velocity::default())
-            .insert(Enemy)
-            .insert(MaxHealth(50.))
-            .insert(CurrentHealth(50.))
-            .insert(Sensor)
-            .insert(EnemyDirection(
-                1.0,
-                Timer::from_seconds(rng.gen_range(0.3_f32..1.2_f32), TimerMode::Repeating),
-            ))
-            .insert(JumpTimer(Timer::from_seconds(10.0, TimerMode::Repeating)));
     }
 }
 
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    animations: &AnimationLibrary,
+    pos: Vec3,
+    turn_secs: f32,
+) {
+    commands
+        .spawn(SpriteSheetBundle {
+            sprite: TextureAtlasSprite {
+                custom_size: Some(Vec2::new(100.0, 100.0)),
+                ..default()
+            },
+            texture_atlas: animations.atlas("bill"),
+            transform: Transform::from_translation(pos),
+            ..default()
+        })
+        .insert(Animator::new("bill", AnimationClip::Run))
+        // .insert(RigidBody::Dynamic)
+        .insert(Collider::cuboid(50.0, 50.0))
+        .insert(Enemy)
+        .insert(EnemyKind(ENEMY_KINDS[0]))
+        .insert(MaxHealth(50.))
+        .insert(CurrentHealth(50.))
+        .insert(ContactDamage(35.))
+        .insert(Sensor)
+        .insert(EnemyDirection(
+            1.0,
+            Timer::from_seconds(turn_secs, TimerMode::Repeating),
+        ))
+        .insert(JumpTimer(Timer::from_seconds(10.0, TimerMode::Repeating)))
+        .insert(RunEntity);
+}
+
 #[derive(Component)]
 pub struct DespawnTimer(Timer);
 
-pub fn tick_month(
-    mut month: ResMut<MonthTimer>,
-    mut text: Query<&mut Text, With<DayText>>,
-    time: Res<Time>,
+pub fn pay_rent(
+    mut month_ended: EventReader<MonthEnded>,
     mut rent_tracker: Query<&mut MoneyText>,
-    asset_server: Res<AssetServer>,
-    mut commands: Commands,
-    mut player: Query<&mut CurrentHealth, With<Player>>,
+    mut toasts: ResMut<Toasts>,
+    mut player: Query<(&mut CurrentHealth, &MaxHealth), With<Player>>,
+    mut rent_paid: EventWriter<RentPaid>,
+    mut rent_missed: EventWriter<RentMissed>,
+    difficulty: Res<Difficulty>,
+    mut coupons: ResMut<Coupons>,
 ) {
-    month.0.tick(time.delta());
-    for mut t in text.iter_mut() {
-        println!(
-            "DAY {} {:?}",
-            month.0.elapsed().as_secs(),
-            f32::floor(month.0.elapsed().as_secs() as f32 / 2.5)
-        );
-        t.sections[0].value = format!(
-            "Day {}",
-            f32::floor(month.0.elapsed().as_secs() as f32 / 2.5)
-        );
-    }
-    if month.0.finished() {
+    for _ in month_ended.iter() {
         let mut rent = rent_tracker.single_mut();
-        if rent.0 < rent.1 {
-            commands.spawn((
-                DespawnTimer(Timer::from_seconds(5., TimerMode::Once)),
-                // Create a TextBundle that has a Text with a single section.
-                TextBundle::from_section(
-                    // Accepts a `String` or any type that converts into a `String`, such as `&str`
-                    "NOT ENOUGH MONEY FOR RENT: CREDIT SCORE LOWERED",
-                    TextStyle {
-                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                        font_size: 50.0,
-                        color: Color::ORANGE_RED,
-                    },
-                ) // Set the alignment of the Text
-                .with_text_alignment(TextAlignment::Left)
-                // Set the style of the TextBundle itself.
-                .with_style(Style {
-                    position_type: PositionType::Absolute,
-                    position: UiRect {
-                        top: Val::Px(200.0),
-                        left: Val::Px(100.0),
-                        ..default()
-                    },
-                    ..default()
-                }),
-            ));
-            player.single_mut().0 -= 30.;
+        let due = (rent.1 - coupons.0).max(0);
+        if rent.0 < due {
+            toasts.notify(
+                ToastKind::Danger,
+                "NOT ENOUGH MONEY FOR RENT: CREDIT SCORE LOWERED",
+                5.,
+            );
+            player.single_mut().0 .0 -= RENT_CREDIT;
+            rent_missed.send(RentMissed);
         } else {
-            rent.0 -= rent.1;
-            player.single_mut().0 += 30.;
+            rent.0 -= due;
+            coupons.0 = 0;
+            let (mut credit, max) = player.single_mut();
+            credit.0 = (credit.0 + RENT_CREDIT).min(max.0);
+            toasts.notify(ToastKind::Success, format!("RENT PAID: ${}", due), 3.);
+            rent_paid.send(RentPaid);
         }
-        rent.1 += 200;
-    }
-
-    if player.single_mut().0 <= 0. {
-        commands.spawn((
-            DespawnTimer(Timer::from_seconds(5., TimerMode::Once)),
-            // Create a TextBundle that has a Text with a single section.
-            TextBundle::from_section(
-                // Accepts a `String` or any type that converts into a `String`, such as `&str`
-                "CREDIT SCORE TOO LOW: GAME OVER",
-                TextStyle {
-                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                    font_size: 50.0,
-                    color: Color::RED,
-                },
-            ) // Set the alignment of the Text
-            .with_text_alignment(TextAlignment::Left)
-            // Set the style of the TextBundle itself.
-            .with_style(Style {
-                position_type: PositionType::Absolute,
-                position: UiRect {
-                    top: Val::Px(200.0),
-                    left: Val::Px(100.0),
-                    ..default()
-                },
-                ..default()
-            }),
-        ));
+        rent.1 += difficulty.rent_increase();
     }
 }
 
@@ -0,0 +1,232 @@
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{
+    achievements::Achievement,
+    events::StartRun,
+    history::{open_history, HistorySort, RunHistory},
+    level::{CurrentLevel, LEVELS},
+    profile::Profile,
+    run::Difficulty,
+    save::{LoadedSave, SaveData},
+    settings::{close_settings, open_settings, SettingsPanel},
+    ui::{overlay, spawn_button, spawn_title, ButtonPressed, FONT},
+};
+
+#[derive(Component)]
+pub struct MainMenu;
+
+/// Difficulty and stage pickers, stats and achievements, opened on top of the main menu.
+#[derive(Component)]
+pub struct SubMenu;
+
+#[derive(Component, Clone, Copy)]
+pub enum MainMenuButton {
+    NewGame,
+    PickStage(Difficulty),
+    /// Difficulty and index into `LEVELS`.
+    Start(Difficulty, usize),
+    Continue,
+    Settings,
+    Stats,
+    Achievements,
+    History,
+    SortHistory(HistorySort),
+    Quit,
+    Back,
+}
+
+pub fn open_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    let (mut node, layer) = overlay(10);
+    // Nothing is running behind the title screen
+    node.background_color = Color::rgb(0.08, 0.1, 0.16).into();
+    commands
+        .spawn((node, layer, MainMenu))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), "Credit Score");
+            spawn_button(parent, font.clone(), "New Game", MainMenuButton::NewGame);
+            // A save that no longer parses can't be continued
+            if SaveData::load().is_some() {
+                spawn_button(parent, font.clone(), "Continue", MainMenuButton::Continue);
+            }
+            spawn_button(parent, font.clone(), "Settings", MainMenuButton::Settings);
+            spawn_button(parent, font.clone(), "Stats", MainMenuButton::Stats);
+            spawn_button(
+                parent,
+                font.clone(),
+                "Achievements",
+                MainMenuButton::Achievements,
+            );
+            spawn_button(parent, font.clone(), "High Scores", MainMenuButton::History);
+            spawn_button(parent, font, "Quit", MainMenuButton::Quit);
+        });
+}
+
+pub fn close_main_menu(
+    mut commands: Commands,
+    menus: Query<Entity, With<MainMenu>>,
+    sub_menus: Query<Entity, With<SubMenu>>,
+    panels: Query<Entity, With<SettingsPanel>>,
+) {
+    for menu in menus.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+    close_sub_menus(&mut commands, &sub_menus);
+    close_settings(&mut commands, &panels);
+}
+
+pub fn close_sub_menus(commands: &mut Commands, sub_menus: &Query<Entity, With<SubMenu>>) {
+    for menu in sub_menus.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
+
+fn open_difficulty_menu(commands: &mut Commands, asset_server: &AssetServer) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    commands
+        .spawn((overlay(20), SubMenu))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), "Difficulty");
+            for difficulty in Difficulty::ALL {
+                spawn_button(
+                    parent,
+                    font.clone(),
+                    difficulty.label(),
+                    MainMenuButton::PickStage(difficulty),
+                );
+            }
+            spawn_button(parent, font, "Back", MainMenuButton::Back);
+        });
+}
+
+fn open_stage_menu(commands: &mut Commands, asset_server: &AssetServer, difficulty: Difficulty) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    commands
+        .spawn((overlay(30), SubMenu))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), "Stage");
+            for (index, (name, _)) in LEVELS.iter().enumerate() {
+                spawn_button(
+                    parent,
+                    font.clone(),
+                    name,
+                    MainMenuButton::Start(difficulty, index),
+                );
+            }
+            spawn_button(parent, font, "Back", MainMenuButton::Back);
+        });
+}
+
+fn open_stats(commands: &mut Commands, asset_server: &AssetServer, profile: &Profile) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    let lines = [
+        format!("Runs started: {}", profile.runs_started),
+        format!("Bills destroyed: {}", profile.bills_destroyed),
+        format!("Rent paid: {} times", profile.rent_paid),
+        format!("Rent missed: {} times", profile.rent_missed),
+        format!("Most earned in a run: ${}", profile.most_cash_earned),
+    ];
+    commands
+        .spawn((overlay(20), SubMenu))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), "Stats");
+            for line in lines {
+                parent.spawn(TextBundle::from_section(
+                    line,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 28.0,
+                        color: Color::WHITE,
+                    },
+                ));
+            }
+            spawn_button(parent, font, "Back", MainMenuButton::Back);
+        });
+}
+
+fn open_achievements(commands: &mut Commands, asset_server: &AssetServer, profile: &Profile) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    let title = format!(
+        "Achievements {}/{}",
+        profile.achievements.len(),
+        Achievement::ALL.len()
+    );
+    commands
+        .spawn((overlay(20), SubMenu))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), &title);
+            for achievement in Achievement::ALL {
+                let (mark, color) = if profile.achievements.contains(&achievement) {
+                    ("[x]", Color::GREEN)
+                } else {
+                    ("[ ]", Color::GRAY)
+                };
+                parent.spawn(TextBundle::from_section(
+                    format!(
+                        "{} {}: {}",
+                        mark,
+                        achievement.name(),
+                        achievement.description()
+                    ),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 28.0,
+                        color,
+                    },
+                ));
+            }
+            spawn_button(parent, font, "Back", MainMenuButton::Back);
+        });
+}
+
+pub fn main_menu_buttons(
+    mut pressed: EventReader<ButtonPressed>,
+    buttons: Query<&MainMenuButton>,
+    sub_menus: Query<Entity, With<SubMenu>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    profile: Res<Profile>,
+    history: Res<RunHistory>,
+    mut difficulty: ResMut<Difficulty>,
+    mut level: ResMut<CurrentLevel>,
+    mut loaded_save: ResMut<LoadedSave>,
+    mut start_run: EventWriter<StartRun>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for button in pressed.iter().filter_map(|p| buttons.get(p.0).ok()) {
+        match *button {
+            MainMenuButton::NewGame => open_difficulty_menu(&mut commands, &asset_server),
+            MainMenuButton::PickStage(choice) => {
+                open_stage_menu(&mut commands, &asset_server, choice)
+            }
+            MainMenuButton::Start(choice, stage) => {
+                SaveData::delete();
+                *difficulty = choice;
+                level.0 = stage;
+                loaded_save.0 = None;
+                start_run.send(StartRun);
+            }
+            MainMenuButton::Continue => {
+                if let Some(save) = SaveData::load() {
+                    *difficulty = save.difficulty;
+                    loaded_save.0 = Some(save);
+                    start_run.send(StartRun);
+                }
+            }
+            MainMenuButton::Settings => open_settings(&mut commands, &asset_server),
+            MainMenuButton::Stats => open_stats(&mut commands, &asset_server, &profile),
+            MainMenuButton::Achievements => {
+                open_achievements(&mut commands, &asset_server, &profile)
+            }
+            MainMenuButton::History => {
+                open_history(&mut commands, &asset_server, &history, HistorySort::Recent)
+            }
+            MainMenuButton::SortHistory(sort) => {
+                close_sub_menus(&mut commands, &sub_menus);
+                open_history(&mut commands, &asset_server, &history, sort);
+            }
+            MainMenuButton::Quit => exit.send(AppExit),
+            MainMenuButton::Back => close_sub_menus(&mut commands, &sub_menus),
+        }
+    }
+}
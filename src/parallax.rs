@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{camera::MainCamera, run::RunEntity};
+
+/// Tiles spawned per layer, centered on the camera. Layers should be at least as wide as the view.
+const TILES_PER_LAYER: i32 = 3;
+
+#[derive(Deserialize)]
+pub struct BackgroundLayerData {
+    /// Plain color rect when there's no texture, e.g. a sky.
+    #[serde(default)]
+    pub texture: Option<String>,
+    #[serde(default = "white")]
+    pub color: (f32, f32, f32),
+    pub size: (f32, f32),
+    /// Fraction of the camera's movement the layer follows: 0 scrolls with the world,
+    /// 1 stays fixed on screen.
+    pub parallax: f32,
+    #[serde(default)]
+    pub offset: (f32, f32),
+}
+
+fn white() -> (f32, f32, f32) {
+    (1., 1., 1.)
+}
+
+#[derive(Component)]
+pub struct ParallaxLayer {
+    pub parallax: f32,
+    pub width: f32,
+    pub offset: Vec2,
+}
+
+/// Spawns a layer as a row of tiles, earlier layers are drawn further back.
+pub fn spawn_background_layer(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    layer: &BackgroundLayerData,
+    index: usize,
+) {
+    let size = Vec2::new(layer.size.0, layer.size.1);
+    let (r, g, b) = layer.color;
+    let texture = layer
+        .texture
+        .as_ref()
+        .map(|path| asset_server.load(path.as_str()))
+        .unwrap_or_default();
+
+    commands
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_translation(Vec3::new(
+                layer.offset.0,
+                layer.offset.1,
+                index as f32 * 0.01,
+            ))),
+            ParallaxLayer {
+                parallax: layer.parallax,
+                width: size.x,
+                offset: Vec2::new(layer.offset.0, layer.offset.1),
+            },
+            RunEntity,
+        ))
+        .with_children(|parent| {
+            for i in -(TILES_PER_LAYER / 2)..=TILES_PER_LAYER / 2 {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgb(r, g, b),
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    texture: texture.clone(),
+                    transform: Transform::from_translation(Vec3::new(i as f32 * size.x, 0., 0.)),
+                    ..default()
+                });
+            }
+        });
+}
+
+pub fn update_parallax(
+    camera: Query<&Transform, (With<MainCamera>, Without<ParallaxLayer>)>,
+    mut layers: Query<(&mut Transform, &ParallaxLayer)>,
+) {
+    let Ok(cam) = camera.get_single() else {
+        return;
+    };
+
+    for (mut t, layer) in layers.iter_mut() {
+        let follow = cam.translation.truncate() * layer.parallax;
+        // Jump whole tiles so the middle tile is always the one under the camera
+        let scrolled = cam.translation.x - follow.x - layer.offset.x;
+        let snap = (scrolled / layer.width).round() * layer.width;
+        t.translation.x = layer.offset.x + follow.x + snap;
+        t.translation.y = layer.offset.y + follow.y;
+    }
+}
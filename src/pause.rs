@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use bevy_rapier2d::{control::KinematicCharacterController, plugin::RapierConfiguration};
+
+use crate::{
+    events::StartRun,
+    expenses::{close_bills, open_bills, Bills, BillsPanel},
+    menu::{close_sub_menus, SubMenu},
+    settings::{close_settings, open_settings, SettingsPanel},
+    ui::{overlay, spawn_button, spawn_title, ButtonPressed, FONT},
+    GameState,
+};
+
+#[derive(Component)]
+pub struct PauseMenu;
+
+#[derive(Component, Clone, Copy)]
+pub enum PauseButton {
+    Resume,
+    Bills,
+    Settings,
+    Restart,
+    Quit,
+}
+
+pub fn toggle_pause(
+    key_input: Res<Input<KeyCode>>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    panels: Query<Entity, With<SettingsPanel>>,
+    sub_menus: Query<Entity, With<SubMenu>>,
+    bills_panels: Query<Entity, With<BillsPanel>>,
+    mut commands: Commands,
+) {
+    if !key_input.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    match state.0 {
+        GameState::MainMenu => {
+            close_settings(&mut commands, &panels);
+            close_sub_menus(&mut commands, &sub_menus);
+        }
+        GameState::Playing => next_state.set(GameState::Paused),
+        // Escape backs out of settings and bills first
+        GameState::Paused if !panels.is_empty() => close_settings(&mut commands, &panels),
+        GameState::Paused if !bills_panels.is_empty() => close_bills(&mut commands, &bills_panels),
+        GameState::Paused => next_state.set(GameState::Playing),
+        GameState::LifeEvent | GameState::PerkSelect | GameState::GameOver => {}
+    }
+}
+
+/// Gameplay systems stop with the state, physics has to be stopped by hand.
+pub fn freeze_physics(
+    mut rapier: ResMut<RapierConfiguration>,
+    mut controllers: Query<&mut KinematicCharacterController>,
+) {
+    rapier.physics_pipeline_active = false;
+    for mut controller in controllers.iter_mut() {
+        controller.translation = None;
+    }
+}
+
+pub fn resume_physics(mut rapier: ResMut<RapierConfiguration>) {
+    rapier.physics_pipeline_active = true;
+}
+
+pub fn open_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    commands
+        .spawn((overlay(10), PauseMenu))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), "Paused");
+            spawn_button(parent, font.clone(), "Resume", PauseButton::Resume);
+            spawn_button(parent, font.clone(), "Bills", PauseButton::Bills);
+            spawn_button(parent, font.clone(), "Settings", PauseButton::Settings);
+            spawn_button(parent, font.clone(), "Restart", PauseButton::Restart);
+            spawn_button(parent, font, "Quit to Menu", PauseButton::Quit);
+        });
+}
+
+pub fn close_pause_menu(
+    mut commands: Commands,
+    menus: Query<Entity, With<PauseMenu>>,
+    panels: Query<Entity, With<SettingsPanel>>,
+    bills_panels: Query<Entity, With<BillsPanel>>,
+) {
+    for menu in menus.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+    close_settings(&mut commands, &panels);
+    close_bills(&mut commands, &bills_panels);
+}
+
+pub fn pause_buttons(
+    mut pressed: EventReader<ButtonPressed>,
+    buttons: Query<&PauseButton>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    bills: Res<Bills>,
+    mut start_run: EventWriter<StartRun>,
+) {
+    for button in pressed.iter().filter_map(|p| buttons.get(p.0).ok()) {
+        match button {
+            PauseButton::Resume => next_state.set(GameState::Playing),
+            PauseButton::Bills => open_bills(&mut commands, &asset_server, &bills),
+            PauseButton::Settings => open_settings(&mut commands, &asset_server),
+            PauseButton::Restart => start_run.send(StartRun),
+            PauseButton::Quit => next_state.set(GameState::MainMenu),
+        }
+    }
+}
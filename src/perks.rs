@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+use rand::seq::IteratorRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::load_config,
+    ending::RunStats,
+    events::MonthEnded,
+    run::{GameRng, RunModifiers},
+    toast::{ToastKind, Toasts},
+    ui::{overlay, spawn_sized_button, spawn_title, ButtonPressed, InputGrace, FONT},
+    GameState, MaxHealth, MoneyText, Player,
+};
+
+const PERKS: &str = "config/perks.ron";
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub enum PerkEffect {
+    MaxCredit(f32),
+    /// Added to the throw rate multiplier.
+    ThrowRate(f32),
+    /// Added to the cash drop multiplier.
+    Bounty(f32),
+    /// Taken off the rent for good.
+    RentDiscount(i32),
+    ExtraJump,
+    /// Added to the rare drop multiplier.
+    Luck(f32),
+}
+
+#[derive(Deserialize)]
+pub struct PerkData {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub effect: PerkEffect,
+    pub max_stacks: usize,
+}
+
+#[derive(Resource, Deserialize, Default)]
+pub struct PerkPool {
+    /// How many perks each month end offers.
+    pub choices: usize,
+    pub perks: Vec<PerkData>,
+}
+
+impl PerkPool {
+    pub fn load() -> PerkPool {
+        load_config(PERKS)
+    }
+}
+
+/// Perks picked this run, repeated once per stack, and the ones on offer right now.
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunPerks {
+    pub taken: Vec<String>,
+    #[serde(skip)]
+    pub offered: Vec<usize>,
+}
+
+impl RunPerks {
+    pub fn stacks(&self, id: &str) -> usize {
+        self.taken.iter().filter(|taken| *taken == id).count()
+    }
+}
+
+pub fn offer_perks(
+    mut month_ended: EventReader<MonthEnded>,
+    stats: Res<RunStats>,
+    pool: Res<PerkPool>,
+    mut perks: ResMut<RunPerks>,
+    mut rng: ResMut<GameRng>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if month_ended.iter().count() == 0 || stats.ending.is_some() {
+        return;
+    }
+    let offered = (0..pool.perks.len())
+        .filter(|i| perks.stacks(&pool.perks[*i].id) < pool.perks[*i].max_stacks)
+        .choose_multiple(&mut **rng, pool.choices);
+    if offered.is_empty() {
+        return;
+    }
+    perks.offered = offered;
+    next_state.set(GameState::PerkSelect);
+}
+
+#[derive(Component)]
+pub struct PerkMenu;
+
+#[derive(Component, Clone, Copy)]
+pub struct PerkButton(pub usize);
+
+pub fn open_perk_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    pool: Res<PerkPool>,
+    perks: Res<RunPerks>,
+) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    commands
+        .spawn((overlay(10), PerkMenu, InputGrace::default()))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), "MONTH END: PICK A PERK");
+            for index in perks.offered.iter() {
+                let perk = &pool.perks[*index];
+                let stacks = perks.stacks(&perk.id);
+                let label = if stacks > 0 {
+                    format!("{} ({}): {}", perk.name, stacks + 1, perk.description)
+                } else {
+                    format!("{}: {}", perk.name, perk.description)
+                };
+                spawn_sized_button(
+                    parent,
+                    font.clone(),
+                    &label,
+                    Size::new(Val::Px(640.), Val::Px(60.)),
+                    PerkButton(*index),
+                );
+            }
+        });
+}
+
+pub fn close_perk_menu(mut commands: Commands, menus: Query<Entity, With<PerkMenu>>) {
+    for menu in menus.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
+
+pub fn perk_buttons(
+    mut pressed: EventReader<ButtonPressed>,
+    buttons: Query<&PerkButton>,
+    pool: Res<PerkPool>,
+    mut perks: ResMut<RunPerks>,
+    mut modifiers: ResMut<RunModifiers>,
+    mut player: Query<&mut MaxHealth, With<Player>>,
+    mut money: Query<&mut MoneyText>,
+    mut toasts: ResMut<Toasts>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(button) = pressed.iter().find_map(|p| buttons.get(p.0).ok()) else {
+        return;
+    };
+    let perk = &pool.perks[button.0];
+    match perk.effect {
+        PerkEffect::MaxCredit(amount) => {
+            modifiers.max_credit += amount;
+            player.single_mut().0 += amount;
+        }
+        PerkEffect::ThrowRate(amount) => modifiers.throw_rate += amount,
+        PerkEffect::Bounty(amount) => modifiers.bounty += amount,
+        PerkEffect::RentDiscount(amount) => {
+            let mut money = money.single_mut();
+            money.1 = (money.1 - amount).max(0);
+        }
+        PerkEffect::ExtraJump => modifiers.extra_jumps += 1,
+        PerkEffect::Luck(amount) => modifiers.luck += amount,
+    }
+    perks.taken.push(perk.id.clone());
+    perks.offered.clear();
+    toasts.notify(ToastKind::Success, perk.name.to_uppercase(), 2.);
+    next_state.set(GameState::Playing);
+}
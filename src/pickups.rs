@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+use bevy_rapier2d::{
+    control::{KinematicCharacterController, KinematicCharacterControllerOutput},
+    geometry::{Collider, Sensor},
+    pipeline::QueryFilterFlags,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::CashCollected,
+    hud::CARD_NAMES,
+    loot::Coupons,
+    powerups::PowerUpKind,
+    run::RunEntity,
+    toast::{ToastKind, Toasts},
+    CurrentCard, MoneyText, Player,
+};
+
+/// Most cash in a single coin, bigger drops are split up.
+pub const COIN_VALUE: i32 = 50;
+const COIN_SIZE: f32 = 20.;
+const ITEM_SIZE: f32 = 28.;
+const COIN_GRAVITY: f32 = -800.;
+const PICKUP_SECS: f32 = 8.;
+/// Coins blink for the last few seconds before they vanish.
+const BLINK_SECS: f32 = 2.;
+/// Close enough to grab without a magnet.
+const COLLECT_RADIUS: f32 = 60.;
+const MAGNET_SPEED: f32 = 500.;
+/// Share of the vertical speed kept when a coin hits the ground.
+const BOUNCE: f32 = 0.5;
+const MIN_BOUNCE_SPEED: f32 = 40.;
+const GROUND_FRICTION: f32 = 4.;
+
+/// Magnet radius for each level, level 0 is no magnet.
+pub const MAGNET_RADII: [f32; 3] = [0., 180., 320.];
+/// Price of the next level, indexed by the current one.
+pub const MAGNET_COSTS: [i32; 2] = [500, 1500];
+
+#[derive(Clone, Copy, Debug)]
+pub enum PickupItem {
+    Cash(i32),
+    Coupon(i32),
+    Card(i32),
+    PowerUp(PowerUpKind, f32),
+}
+
+impl PickupItem {
+    fn look(self) -> (Color, f32) {
+        match self {
+            PickupItem::Cash(_) => (Color::GOLD, COIN_SIZE),
+            PickupItem::Coupon(_) => (Color::PINK, ITEM_SIZE),
+            PickupItem::Card(_) => (Color::CYAN, ITEM_SIZE),
+            PickupItem::PowerUp(kind, _) => (kind.color(), ITEM_SIZE),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Pickup {
+    item: PickupItem,
+    velocity: Vec2,
+    life: Timer,
+}
+
+/// Pulls nearby cash in. Bought with E during a run.
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct Magnet {
+    pub level: usize,
+}
+
+impl Magnet {
+    /// Levels past the last radius, e.g. from an edited save, get the biggest one.
+    pub fn radius(&self) -> f32 {
+        MAGNET_RADII[self.level.min(MAGNET_RADII.len() - 1)]
+    }
+
+    pub fn next_cost(&self) -> Option<i32> {
+        MAGNET_COSTS.get(self.level).copied()
+    }
+}
+
+/// Pops an item out of a dead enemy with a bit of random spin.
+pub fn spawn_pickup(commands: &mut Commands, rng: &mut impl Rng, position: Vec3, item: PickupItem) {
+    let (color, size) = item.look();
+    let velocity = Vec2::new(rng.gen_range(-150.0..150.0), rng.gen_range(200.0..350.0));
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(Vec2::splat(size)),
+                ..default()
+            },
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        KinematicCharacterController {
+            filter_flags: QueryFilterFlags::EXCLUDE_SENSORS,
+            ..default()
+        },
+        Collider::ball(size / 2.),
+        Sensor,
+        Pickup {
+            item,
+            velocity,
+            life: Timer::from_seconds(PICKUP_SECS, TimerMode::Once),
+        },
+        RunEntity,
+    ));
+}
+
+pub fn update_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    magnet: Res<Magnet>,
+    player: Query<(Entity, &Transform), With<Player>>,
+    mut pickups: Query<(
+        Entity,
+        &Transform,
+        &mut Pickup,
+        &mut KinematicCharacterController,
+        &mut Visibility,
+        Option<&KinematicCharacterControllerOutput>,
+    )>,
+    mut money: Query<&mut MoneyText>,
+    mut collected: EventWriter<CashCollected>,
+    mut coupons: ResMut<Coupons>,
+    mut card: ResMut<CurrentCard>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Ok((player_entity, player)) = player.get_single() else {
+        return;
+    };
+    let dt = time.delta_seconds();
+    for (entity, t, mut pickup, mut controller, mut visibility, output) in pickups.iter_mut() {
+        let to_player = player.translation.truncate() - t.translation.truncate();
+        let distance = to_player.length();
+        if distance <= COLLECT_RADIUS {
+            match pickup.item {
+                PickupItem::Cash(amount) => {
+                    money.single_mut().0 += amount;
+                    collected.send(CashCollected {
+                        amount,
+                        position: t.translation,
+                    });
+                }
+                PickupItem::Coupon(amount) => {
+                    coupons.0 += amount;
+                    toasts.notify(
+                        ToastKind::Success,
+                        format!("COUPON: ${} OFF RENT", amount),
+                        3.,
+                    );
+                }
+                PickupItem::Card(tier) => {
+                    card.0 = card.0.max(tier);
+                    let name = CARD_NAMES[(tier as usize).min(CARD_NAMES.len() - 1)];
+                    toasts.notify(
+                        ToastKind::Success,
+                        format!("{} UNLOCKED", name.to_uppercase()),
+                        3.,
+                    );
+                }
+                PickupItem::PowerUp(kind, secs) => {
+                    kind.grant(&mut commands.entity(player_entity), secs);
+                    toasts.notify(ToastKind::Info, kind.label().to_uppercase(), 2.);
+                }
+            }
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        pickup.life.tick(time.delta());
+        if pickup.life.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let blink = pickup.life.remaining_secs() < BLINK_SECS
+            && (pickup.life.remaining_secs() * 8.) as i32 % 2 == 0;
+        *visibility = if blink {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+
+        if distance <= magnet.radius() {
+            pickup.velocity = to_player / distance * MAGNET_SPEED;
+        } else {
+            if let Some(output) = output {
+                if output.grounded && pickup.velocity.y < 0. {
+                    pickup.velocity.y = -pickup.velocity.y * BOUNCE;
+                    // Settle instead of jittering on the ground forever
+                    if pickup.velocity.y < MIN_BOUNCE_SPEED {
+                        pickup.velocity.y = 0.;
+                    }
+                    pickup.velocity.x -= pickup.velocity.x * (GROUND_FRICTION * dt).min(1.);
+                }
+            }
+            pickup.velocity.y += COIN_GRAVITY * dt;
+        }
+        controller.translation = Some(pickup.velocity * dt);
+    }
+}
+
+pub fn buy_magnet(
+    key_input: Res<Input<KeyCode>>,
+    mut magnet: ResMut<Magnet>,
+    mut money: Query<&mut MoneyText>,
+) {
+    if !key_input.just_pressed(KeyCode::E) {
+        return;
+    }
+    let (Some(cost), Ok(mut money)) = (magnet.next_cost(), money.get_single_mut()) else {
+        return;
+    };
+    if money.0 >= cost {
+        money.0 -= cost;
+        magnet.level += 1;
+    }
+}
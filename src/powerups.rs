@@ -0,0 +1,127 @@
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use serde::Deserialize;
+
+use crate::Player;
+
+/// Time between throws while rapid fire is held down.
+pub const RAPID_FIRE_SECS: f32 = 0.12;
+/// Enemy speed while slowed.
+pub const SLOW_ENEMIES_FACTOR: f32 = 0.4;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PowerUpKind {
+    DoubleBounty,
+    RapidFire,
+    Shield,
+    SlowEnemies,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 4] = [
+        PowerUpKind::DoubleBounty,
+        PowerUpKind::RapidFire,
+        PowerUpKind::Shield,
+        PowerUpKind::SlowEnemies,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PowerUpKind::DoubleBounty => "Double Bounty",
+            PowerUpKind::RapidFire => "Rapid Fire",
+            PowerUpKind::Shield => "Shield",
+            PowerUpKind::SlowEnemies => "Slow Bills",
+        }
+    }
+
+    /// Short text for the HUD icon.
+    pub fn icon(self) -> &'static str {
+        match self {
+            PowerUpKind::DoubleBounty => "2x",
+            PowerUpKind::RapidFire => ">>",
+            PowerUpKind::Shield => "[]",
+            PowerUpKind::SlowEnemies => "~",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            PowerUpKind::DoubleBounty => Color::GOLD,
+            PowerUpKind::RapidFire => Color::ORANGE,
+            PowerUpKind::Shield => Color::AQUAMARINE,
+            PowerUpKind::SlowEnemies => Color::VIOLET,
+        }
+    }
+
+    /// Puts the effect on the player. Picking up one that is already running restarts its timer.
+    pub fn grant(self, player: &mut EntityCommands, secs: f32) {
+        let timer = Timer::from_seconds(secs, TimerMode::Once);
+        match self {
+            PowerUpKind::DoubleBounty => player.insert(DoubleBounty(timer)),
+            PowerUpKind::RapidFire => player.insert(RapidFire {
+                timer,
+                cooldown: Timer::from_seconds(RAPID_FIRE_SECS, TimerMode::Repeating),
+            }),
+            PowerUpKind::Shield => player.insert(Shield(timer)),
+            PowerUpKind::SlowEnemies => player.insert(SlowEnemies(timer)),
+        };
+    }
+}
+
+/// Timed effects on the player, removed when the timer runs out.
+pub trait PowerUp: Component {
+    fn timer(&mut self) -> &mut Timer;
+}
+
+/// Enemies drop twice the cash.
+#[derive(Component)]
+pub struct DoubleBounty(pub Timer);
+
+/// Holding the mouse keeps throwing.
+#[derive(Component)]
+pub struct RapidFire {
+    pub timer: Timer,
+    pub cooldown: Timer,
+}
+
+/// Soaks up the next hit, then breaks.
+#[derive(Component)]
+pub struct Shield(pub Timer);
+
+#[derive(Component)]
+pub struct SlowEnemies(pub Timer);
+
+impl PowerUp for DoubleBounty {
+    fn timer(&mut self) -> &mut Timer {
+        &mut self.0
+    }
+}
+
+impl PowerUp for RapidFire {
+    fn timer(&mut self) -> &mut Timer {
+        &mut self.timer
+    }
+}
+
+impl PowerUp for Shield {
+    fn timer(&mut self) -> &mut Timer {
+        &mut self.0
+    }
+}
+
+impl PowerUp for SlowEnemies {
+    fn timer(&mut self) -> &mut Timer {
+        &mut self.0
+    }
+}
+
+pub fn tick_power_up<T: PowerUp>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut T), With<Player>>,
+) {
+    for (entity, mut power_up) in query.iter_mut() {
+        if power_up.timer().tick(time.delta()).finished() {
+            commands.entity(entity).remove::<T>();
+        }
+    }
+}
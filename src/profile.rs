@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    achievements::Achievement,
+    events::{CashCollected, EnemyKilled, RentMissed, RentPaid, StartRun},
+};
+
+const PROFILE_PATH: &str = "profile.ron";
+
+/// Lifetime stats that survive between launches.
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub runs_started: u32,
+    pub bills_destroyed: u32,
+    pub rent_paid: u32,
+    pub rent_missed: u32,
+    pub most_cash_earned: i32,
+    pub achievements: Vec<Achievement>,
+}
+
+impl Profile {
+    pub fn load() -> Profile {
+        let Ok(data) = std::fs::read_to_string(PROFILE_PATH) else {
+            return Profile::default();
+        };
+        ron::from_str(&data).unwrap_or_else(|e| {
+            println!("ignoring broken profile {PROFILE_PATH}: {e}");
+            Profile::default()
+        })
+    }
+
+    pub fn save(&self) {
+        match ron::ser::to_string_pretty(self, default()) {
+            Ok(data) => {
+                if let Err(e) = std::fs::write(PROFILE_PATH, data) {
+                    println!("failed to write profile {PROFILE_PATH}: {e}");
+                }
+            }
+            Err(e) => println!("failed to serialize profile: {e}"),
+        }
+    }
+}
+
+pub fn track_profile(
+    mut profile: ResMut<Profile>,
+    mut run_earnings: Local<i32>,
+    mut start_run: EventReader<StartRun>,
+    mut kills: EventReader<EnemyKilled>,
+    mut collected: EventReader<CashCollected>,
+    mut rent_paid: EventReader<RentPaid>,
+    mut rent_missed: EventReader<RentMissed>,
+) {
+    for _ in start_run.iter() {
+        profile.runs_started += 1;
+        *run_earnings = 0;
+    }
+    for _ in kills.iter() {
+        profile.bills_destroyed += 1;
+    }
+    for cash in collected.iter() {
+        *run_earnings += cash.amount;
+    }
+    if *run_earnings > profile.most_cash_earned {
+        profile.most_cash_earned = *run_earnings;
+    }
+    for _ in rent_paid.iter() {
+        profile.rent_paid += 1;
+    }
+    for _ in rent_missed.iter() {
+        profile.rent_missed += 1;
+    }
+}
+
+/// Written when a run ends or the player quits to the menu. Stats counted in between are only
+/// kept once one of those happens, achievements save as soon as they unlock.
+pub fn save_profile(profile: Res<Profile>) {
+    profile.save();
+}
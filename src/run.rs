@@ -0,0 +1,294 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calendar::{Calendar, CalendarConfig},
+    ending::{Ending, RunStats},
+    events::StartRun,
+    expenses::Bills,
+    income::PayPeriod,
+    life_events::LifeEventState,
+    loot::Coupons,
+    perks::RunPerks,
+    pickups::Magnet,
+    ui::{overlay, spawn_button, spawn_title, ButtonPressed, InputGrace, FONT},
+    CurrentCard, CurrentHealth, GameState, MoneyText, Player, SpawnTimer,
+};
+
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn starting_cash(self) -> i32 {
+        match self {
+            Difficulty::Easy => 1000,
+            Difficulty::Normal | Difficulty::Hard => 0,
+        }
+    }
+
+    pub fn spawn_secs(self) -> f32 {
+        match self {
+            Difficulty::Easy => 4.,
+            Difficulty::Normal => 3.,
+            Difficulty::Hard => 2.,
+        }
+    }
+
+    /// Scales the odds of the rarer drops.
+    pub fn drop_rate(self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.25,
+            Difficulty::Normal => 1.,
+            Difficulty::Hard => 0.8,
+        }
+    }
+
+    /// How much the rent goes up after each month.
+    pub fn rent_increase(self) -> i32 {
+        match self {
+            Difficulty::Easy => 100,
+            Difficulty::Normal => 200,
+            Difficulty::Hard => 300,
+        }
+    }
+}
+
+/// The run's only source of randomness, so a seed replays the same run.
+#[derive(Resource)]
+pub struct GameRng {
+    pub seed: u64,
+    rng: StdRng,
+}
+
+impl std::ops::Deref for GameRng {
+    type Target = StdRng;
+
+    fn deref(&self) -> &StdRng {
+        &self.rng
+    }
+}
+
+impl std::ops::DerefMut for GameRng {
+    fn deref_mut(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Carries on a saved run. The generator's state isn't saved, so it's reseeded from how far in
+    /// the run is.
+    pub fn resume(seed: u64, days_played: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed ^ days_played.wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+        }
+    }
+}
+
+/// Multipliers that last for the rest of a run.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunModifiers {
+    /// Scales how often enemies spawn.
+    pub spawn_rate: f32,
+    /// Scales the odds of the rarer drops.
+    pub luck: f32,
+    /// Added on top of the credit score cap.
+    pub max_credit: f32,
+    /// Scales how often cards can be thrown.
+    #[serde(alias = "throw_speed")]
+    pub throw_rate: f32,
+    /// Scales cash drops.
+    pub bounty: f32,
+    pub extra_jumps: u32,
+}
+
+impl Default for RunModifiers {
+    fn default() -> Self {
+        Self {
+            spawn_rate: 1.,
+            luck: 1.,
+            max_credit: 0.,
+            throw_rate: 1.,
+            bounty: 1.,
+            extra_jumps: 0,
+        }
+    }
+}
+
+/// Run-wide resources added by the later systems, reset and restored as a group.
+#[derive(SystemParam)]
+pub struct RunResources<'w> {
+    pub rng: ResMut<'w, GameRng>,
+    pub modifiers: ResMut<'w, RunModifiers>,
+    pub life_events: ResMut<'w, LifeEventState>,
+    pub magnet: ResMut<'w, Magnet>,
+    pub coupons: ResMut<'w, Coupons>,
+    pub perks: ResMut<'w, RunPerks>,
+}
+
+/// Despawned when a run ends. Only put this on root entities, children go with their parent.
+#[derive(Component)]
+pub struct RunEntity;
+
+/// Systems that tear down the previous run and set up a new one when `StartRun` is sent.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StartRunSet;
+
+#[derive(Component)]
+pub struct GameOverMenu;
+
+#[derive(Component, Clone, Copy)]
+pub enum GameOverButton {
+    Restart,
+    MainMenu,
+}
+
+pub fn despawn_run(mut commands: Commands, run_entities: Query<Entity, With<RunEntity>>) {
+    for entity in run_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn reset_run(
+    mut commands: Commands,
+    run_entities: Query<Entity, With<RunEntity>>,
+    difficulty: Res<Difficulty>,
+    mut card: ResMut<CurrentCard>,
+    mut spawn_timer: ResMut<SpawnTimer>,
+    calendar_config: Res<CalendarConfig>,
+    mut calendar: ResMut<Calendar>,
+    mut pay_period: ResMut<PayPeriod>,
+    mut bills: ResMut<Bills>,
+    mut stats: ResMut<RunStats>,
+    mut run: RunResources,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for entity in run_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    card.0 = 0;
+    spawn_timer.0 = Timer::from_seconds(difficulty.spawn_secs(), TimerMode::Repeating);
+    *calendar = Calendar::new(&calendar_config);
+    *pay_period = PayPeriod::default();
+    *bills = Bills::load();
+    *stats = RunStats::default();
+    *run.rng = GameRng::new(rand::random());
+    *run.modifiers = RunModifiers::default();
+    *run.life_events = LifeEventState::default();
+    *run.magnet = Magnet::default();
+    *run.coupons = Coupons::default();
+    *run.perks = RunPerks::default();
+    next_state.set(GameState::Playing);
+}
+
+/// Ending screen with a summary of the run.
+pub fn open_game_over_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    stats: Res<RunStats>,
+    player: Query<&CurrentHealth, With<Player>>,
+    money: Query<&MoneyText>,
+) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    let ending = stats.ending.unwrap_or(Ending::Bankrupt);
+    let credit = player.get_single().map_or(0., |c| c.0);
+    let cash = money.get_single().map_or(0, |m| m.0);
+    let lines = [
+        format!("Months survived: {}", stats.months),
+        format!(
+            "Rent paid: {}  Missed: {}",
+            stats.rent_paid, stats.rent_missed
+        ),
+        format!("Bills destroyed: {}", stats.kills),
+        format!(
+            "Cash earned: ${}  Peak: ${}",
+            stats.cash_earned, stats.peak_cash
+        ),
+        format!("Final cash: ${}", cash),
+        format!("Final credit score: {:.0}", credit),
+    ];
+    let color = if ending.is_win() {
+        Color::GOLD
+    } else {
+        Color::ORANGE_RED
+    };
+    commands
+        .spawn((overlay(10), GameOverMenu, InputGrace::default()))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), ending.title());
+            parent.spawn(
+                TextBundle::from_section(
+                    ending.description(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 28.0,
+                        color,
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(20.)),
+                    ..default()
+                }),
+            );
+            for line in lines {
+                parent.spawn(TextBundle::from_section(
+                    line,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 24.0,
+                        color: Color::WHITE,
+                    },
+                ));
+            }
+            spawn_button(parent, font.clone(), "Play Again", GameOverButton::Restart);
+            spawn_button(parent, font, "Main Menu", GameOverButton::MainMenu);
+        });
+}
+
+pub fn close_game_over_menu(mut commands: Commands, menus: Query<Entity, With<GameOverMenu>>) {
+    for menu in menus.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
+
+pub fn game_over_buttons(
+    mut pressed: EventReader<ButtonPressed>,
+    buttons: Query<&GameOverButton>,
+    key_input: Res<Input<KeyCode>>,
+    mut start_run: EventWriter<StartRun>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if key_input.just_pressed(KeyCode::R) {
+        start_run.send(StartRun);
+    }
+    for button in pressed.iter().filter_map(|p| buttons.get(p.0).ok()) {
+        match button {
+            GameOverButton::Restart => start_run.send(StartRun),
+            GameOverButton::MainMenu => next_state.set(GameState::MainMenu),
+        }
+    }
+}
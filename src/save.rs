@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calendar::Calendar,
+    ending::RunStats,
+    expenses::Bills,
+    income::PayPeriod,
+    level::CurrentLevel,
+    life_events::LifeEventState,
+    loot::Coupons,
+    perks::RunPerks,
+    pickups::Magnet,
+    run::{Difficulty, GameRng, RunModifiers, RunResources},
+    CurrentCard, CurrentHealth, MoneyText, Player,
+};
+
+const SAVE_PATH: &str = "save.ron";
+
+/// Snapshot of a run in progress, written when quitting to the menu.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SaveData {
+    pub difficulty: Difficulty,
+    pub level: usize,
+    pub cash: i32,
+    pub rent: i32,
+    pub credit: f32,
+    pub card: i32,
+    #[serde(default = "Calendar::load")]
+    pub calendar: Calendar,
+    #[serde(default)]
+    pub pay_period: PayPeriod,
+    #[serde(default = "Bills::load")]
+    pub bills: Bills,
+    #[serde(default)]
+    pub stats: RunStats,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub modifiers: RunModifiers,
+    #[serde(default)]
+    pub life_events: LifeEventState,
+    #[serde(default)]
+    pub magnet: Magnet,
+    #[serde(default)]
+    pub coupons: Coupons,
+    #[serde(default)]
+    pub perks: RunPerks,
+}
+
+impl SaveData {
+    pub fn exists() -> bool {
+        std::path::Path::new(SAVE_PATH).exists()
+    }
+
+    pub fn load() -> Option<SaveData> {
+        let data = std::fs::read_to_string(SAVE_PATH).ok()?;
+        match ron::from_str(&data) {
+            Ok(save) => Some(save),
+            Err(e) => {
+                println!("ignoring broken save {SAVE_PATH}: {e}");
+                None
+            }
+        }
+    }
+
+    pub fn delete() {
+        if SaveData::exists() {
+            if let Err(e) = std::fs::remove_file(SAVE_PATH) {
+                println!("failed to delete save {SAVE_PATH}: {e}");
+            }
+        }
+    }
+}
+
+/// Save picked with Continue, consumed by the next `setup`.
+#[derive(Resource, Default)]
+pub struct LoadedSave(pub Option<SaveData>);
+
+pub fn save_run(
+    player: Query<&CurrentHealth, With<Player>>,
+    money: Query<&MoneyText>,
+    difficulty: Res<Difficulty>,
+    level: Res<CurrentLevel>,
+    card: Res<CurrentCard>,
+    calendar: Res<Calendar>,
+    pay_period: Res<PayPeriod>,
+    bills: Res<Bills>,
+    stats: Res<RunStats>,
+    rng: Res<GameRng>,
+    modifiers: Res<RunModifiers>,
+    life_events: Res<LifeEventState>,
+    magnet: Res<Magnet>,
+    coupons: Res<Coupons>,
+    perks: Res<RunPerks>,
+) {
+    let (Ok(credit), Ok(money)) = (player.get_single(), money.get_single()) else {
+        return;
+    };
+    // A finished run has nothing to continue
+    if stats.ending.is_some() {
+        return;
+    }
+    let save = SaveData {
+        difficulty: *difficulty,
+        level: level.0,
+        cash: money.0,
+        rent: money.1,
+        credit: credit.0,
+        card: card.0,
+        calendar: calendar.clone(),
+        pay_period: pay_period.clone(),
+        bills: bills.clone(),
+        stats: stats.clone(),
+        seed: rng.seed,
+        modifiers: modifiers.clone(),
+        life_events: life_events.clone(),
+        magnet: magnet.clone(),
+        coupons: coupons.clone(),
+        perks: perks.clone(),
+    };
+    match ron::ser::to_string_pretty(&save, default()) {
+        Ok(data) => {
+            if let Err(e) = std::fs::write(SAVE_PATH, data) {
+                println!("failed to write save {SAVE_PATH}: {e}");
+            }
+        }
+        Err(e) => println!("failed to serialize save: {e}"),
+    }
+}
+
+/// Puts back the run-wide resources for Continue, after `reset_run` cleared them.
+pub fn restore_save(
+    loaded_save: Res<LoadedSave>,
+    mut level: ResMut<CurrentLevel>,
+    mut card: ResMut<CurrentCard>,
+    mut calendar: ResMut<Calendar>,
+    mut pay_period: ResMut<PayPeriod>,
+    mut bills: ResMut<Bills>,
+    mut stats: ResMut<RunStats>,
+    mut run: RunResources,
+) {
+    let Some(save) = &loaded_save.0 else {
+        return;
+    };
+    level.0 = save.level;
+    card.0 = save.card;
+    *calendar = save.calendar.clone();
+    *pay_period = save.pay_period.clone();
+    *bills = save.bills.clone();
+    *stats = save.stats.clone();
+    *run.rng = GameRng::resume(save.seed, save.calendar.days_played);
+    *run.modifiers = save.modifiers.clone();
+    *run.life_events = save.life_events.clone();
+    *run.magnet = save.magnet.clone();
+    *run.coupons = save.coupons.clone();
+    *run.perks = save.perks.clone();
+}
+
+pub fn delete_save() {
+    SaveData::delete();
+}
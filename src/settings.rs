@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+
+use crate::{
+    audio::{AudioSettings, SoundCategory},
+    ui::{overlay, spawn_button, spawn_sized_button, spawn_title, ButtonPressed, FONT},
+};
+
+/// Volume change per click.
+const VOLUME_STEP: f32 = 0.1;
+
+#[derive(Component)]
+pub struct SettingsPanel;
+
+#[derive(Component, Clone, Copy)]
+pub enum SettingsButton {
+    Volume(Volume, f32),
+    ToggleMute,
+    Back,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Volume {
+    Master,
+    Category(SoundCategory),
+}
+
+impl Volume {
+    const ALL: [Volume; 3] = [
+        Volume::Master,
+        Volume::Category(SoundCategory::Music),
+        Volume::Category(SoundCategory::Sfx),
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Volume::Master => "Master",
+            Volume::Category(SoundCategory::Music) => "Music",
+            Volume::Category(SoundCategory::Sfx) => "Effects",
+        }
+    }
+
+    fn value(self, settings: &AudioSettings) -> f32 {
+        match self {
+            Volume::Master => settings.master,
+            Volume::Category(SoundCategory::Music) => settings.music,
+            Volume::Category(SoundCategory::Sfx) => settings.sfx,
+        }
+    }
+
+    fn value_mut(self, settings: &mut AudioSettings) -> &mut f32 {
+        match self {
+            Volume::Master => &mut settings.master,
+            Volume::Category(SoundCategory::Music) => &mut settings.music,
+            Volume::Category(SoundCategory::Sfx) => &mut settings.sfx,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct VolumeText(pub Volume);
+
+/// Opens the settings panel on top of whichever menu asked for it.
+pub fn open_settings(commands: &mut Commands, asset_server: &AssetServer) {
+    let font: Handle<Font> = asset_server.load(FONT);
+    commands
+        .spawn((overlay(20), SettingsPanel))
+        .with_children(|parent| {
+            spawn_title(parent, font.clone(), "Settings");
+            for volume in Volume::ALL {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            gap: Size::width(Val::Px(10.)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn((
+                            TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 28.0,
+                                    color: Color::WHITE,
+                                },
+                            )
+                            .with_style(Style {
+                                size: Size::width(Val::Px(200.)),
+                                ..default()
+                            }),
+                            VolumeText(volume),
+                        ));
+                        let size = Size::new(Val::Px(50.), Val::Px(50.));
+                        spawn_sized_button(
+                            row,
+                            font.clone(),
+                            "-",
+                            size,
+                            SettingsButton::Volume(volume, -VOLUME_STEP),
+                        );
+                        spawn_sized_button(
+                            row,
+                            font.clone(),
+                            "+",
+                            size,
+                            SettingsButton::Volume(volume, VOLUME_STEP),
+                        );
+                    });
+            }
+            spawn_button(parent, font.clone(), "", SettingsButton::ToggleMute);
+            spawn_button(parent, font, "Back", SettingsButton::Back);
+        });
+}
+
+pub fn settings_buttons(
+    mut pressed: EventReader<ButtonPressed>,
+    buttons: Query<&SettingsButton>,
+    panels: Query<Entity, With<SettingsPanel>>,
+    mut settings: ResMut<AudioSettings>,
+    mut commands: Commands,
+) {
+    for button in pressed.iter().filter_map(|p| buttons.get(p.0).ok()) {
+        match *button {
+            SettingsButton::Volume(volume, delta) => {
+                let value = volume.value_mut(&mut settings);
+                *value = (*value + delta).clamp(0., 1.);
+            }
+            SettingsButton::ToggleMute => settings.muted = !settings.muted,
+            SettingsButton::Back => close_settings(&mut commands, &panels),
+        }
+    }
+}
+
+pub fn close_settings(commands: &mut Commands, panels: &Query<Entity, With<SettingsPanel>>) {
+    for panel in panels.iter() {
+        commands.entity(panel).despawn_recursive();
+    }
+}
+
+pub fn update_settings_text(
+    settings: Res<AudioSettings>,
+    mut volume_texts: Query<(&mut Text, &VolumeText)>,
+    added: Query<(), Added<SettingsPanel>>,
+    buttons: Query<(&SettingsButton, &Children)>,
+    mut texts: Query<&mut Text, Without<VolumeText>>,
+) {
+    if !settings.is_changed() && added.is_empty() {
+        return;
+    }
+    for (mut text, volume) in volume_texts.iter_mut() {
+        text.sections[0].value = format!(
+            "{}: {:.0}%",
+            volume.0.label(),
+            volume.0.value(&settings) * 100.
+        );
+    }
+    for (button, children) in buttons.iter() {
+        if !matches!(button, SettingsButton::ToggleMute) {
+            continue;
+        }
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = if settings.muted { "Unmute" } else { "Mute" }.into();
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::ui::FONT;
+
+/// Toasts on screen at once; the rest wait their turn.
+const MAX_VISIBLE: usize = 4;
+/// Seconds spent fading out at the end of a toast's life.
+const FADE_SECS: f32 = 0.4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Danger,
+}
+
+impl ToastKind {
+    fn color(self) -> Color {
+        match self {
+            ToastKind::Info => Color::WHITE,
+            ToastKind::Success => Color::GREEN,
+            ToastKind::Warning => Color::GOLD,
+            ToastKind::Danger => Color::ORANGE_RED,
+        }
+    }
+}
+
+pub struct Toast {
+    pub kind: ToastKind,
+    pub text: String,
+    pub duration: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct Toasts {
+    pending: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn notify(&mut self, kind: ToastKind, text: impl Into<String>, duration: f32) {
+        self.pending.push_back(Toast {
+            kind,
+            text: text.into(),
+            duration,
+        });
+    }
+}
+
+/// Column near the top of the screen that toasts stack in, newest at the bottom.
+#[derive(Component)]
+pub struct ToastStack;
+
+#[derive(Component)]
+pub struct ToastEntry {
+    timer: Timer,
+    color: Color,
+}
+
+pub fn spawn_toast_stack(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Percent(15.),
+                    ..default()
+                },
+                size: Size::width(Val::Percent(100.)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                gap: Size::height(Val::Px(6.)),
+                ..default()
+            },
+            z_index: ZIndex::Global(30),
+            ..default()
+        },
+        ToastStack,
+    ));
+}
+
+pub fn show_toasts(
+    mut commands: Commands,
+    mut toasts: ResMut<Toasts>,
+    stack: Query<(Entity, Option<&Children>), With<ToastStack>>,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok((stack, children)) = stack.get_single() else {
+        return;
+    };
+    let visible = children.map_or(0, |c| c.len());
+    for _ in visible..MAX_VISIBLE {
+        let Some(toast) = toasts.pending.pop_front() else {
+            break;
+        };
+        let color = toast.kind.color();
+        commands.entity(stack).with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            padding: UiRect::new(
+                                Val::Px(16.),
+                                Val::Px(16.),
+                                Val::Px(6.),
+                                Val::Px(6.),
+                            ),
+                            ..default()
+                        },
+                        background_color: Color::rgba(0., 0., 0., 0.7).into(),
+                        ..default()
+                    },
+                    ToastEntry {
+                        timer: Timer::from_seconds(toast.duration, TimerMode::Once),
+                        color,
+                    },
+                ))
+                .with_children(|entry| {
+                    entry.spawn(TextBundle::from_section(
+                        toast.text,
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: 32.0,
+                            color,
+                        },
+                    ));
+                });
+        });
+    }
+}
+
+pub fn update_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut entries: Query<(Entity, &mut ToastEntry, &mut BackgroundColor, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, mut entry, mut background, children) in entries.iter_mut() {
+        entry.timer.tick(time.delta());
+        if entry.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let alpha = (entry.timer.remaining_secs() / FADE_SECS).min(1.);
+        background.0.set_a(0.7 * alpha);
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].style.color = entry.color.with_a(alpha);
+            }
+        }
+    }
+}
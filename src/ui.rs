@@ -0,0 +1,234 @@
+use bevy::prelude::*;
+
+pub const FONT: &str = "fonts/FiraSans-Bold.ttf";
+
+pub const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+pub const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+pub const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+
+const INPUT_GRACE_SECS: f32 = 0.5;
+
+/// Marks the root of a menu; keyboard and gamepad focus stays in the topmost one.
+#[derive(Component)]
+pub struct MenuLayer(pub i32);
+
+/// Sent when a button is clicked, or confirmed while it has focus.
+pub struct ButtonPressed(pub Entity);
+
+/// Put on a menu that pops up mid-play so a jump or throw the player was already
+/// pressing doesn't pick one of its buttons.
+#[derive(Component)]
+pub struct InputGrace(pub Timer);
+
+impl Default for InputGrace {
+    fn default() -> Self {
+        Self(Timer::from_seconds(INPUT_GRACE_SECS, TimerMode::Once))
+    }
+}
+
+impl InputGrace {
+    fn waiting(&self) -> bool {
+        !self.0.finished()
+    }
+}
+
+/// The button that keyboard and gamepad input acts on.
+#[derive(Resource, Default)]
+pub struct MenuFocus(pub Option<Entity>);
+
+/// Full-screen dimmed column that menus are built in.
+pub fn overlay(z: i32) -> (NodeBundle, MenuLayer) {
+    let node = NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            gap: Size::height(Val::Px(10.)),
+            ..default()
+        },
+        background_color: Color::rgba(0., 0., 0., 0.7).into(),
+        z_index: ZIndex::Global(z),
+        ..default()
+    };
+    (node, MenuLayer(z))
+}
+
+pub fn spawn_title(parent: &mut ChildBuilder, font: Handle<Font>, text: &str) {
+    parent.spawn(
+        TextBundle::from_section(
+            text,
+            TextStyle {
+                font,
+                font_size: 60.0,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::bottom(Val::Px(20.)),
+            ..default()
+        }),
+    );
+}
+
+pub fn spawn_button(
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+    label: &str,
+    action: impl Component,
+) -> Entity {
+    spawn_sized_button(
+        parent,
+        font,
+        label,
+        Size::new(Val::Px(260.), Val::Px(50.)),
+        action,
+    )
+}
+
+pub fn spawn_sized_button(
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+    label: &str,
+    size: Size,
+    action: impl Component,
+) -> Entity {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    size,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: NORMAL_BUTTON.into(),
+                ..default()
+            },
+            action,
+        ))
+        .with_children(|button| {
+            button.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font,
+                    font_size: 28.0,
+                    color: Color::WHITE,
+                },
+            ));
+        })
+        .id()
+}
+
+/// Buttons under `entity` in the order they were spawned.
+fn collect_buttons(
+    entity: Entity,
+    children: &Query<&Children>,
+    buttons: &Query<(), With<Button>>,
+    out: &mut Vec<Entity>,
+) {
+    if buttons.contains(entity) {
+        out.push(entity);
+    }
+    if let Ok(kids) = children.get(entity) {
+        for kid in kids.iter() {
+            collect_buttons(*kid, children, buttons, out);
+        }
+    }
+}
+
+pub fn tick_input_grace(time: Res<Time>, mut graces: Query<&mut InputGrace>) {
+    for mut grace in graces.iter_mut() {
+        grace.0.tick(time.delta());
+    }
+}
+
+pub fn menu_navigation(
+    key_input: Res<Input<KeyCode>>,
+    pad_input: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    layers: Query<(Entity, &MenuLayer)>,
+    graces: Query<&InputGrace>,
+    children: Query<&Children>,
+    buttons: Query<(), With<Button>>,
+    interactions: Query<(Entity, &Interaction), Changed<Interaction>>,
+    mut focus: ResMut<MenuFocus>,
+    mut pressed: EventWriter<ButtonPressed>,
+) {
+    let mut order = Vec::new();
+    let mut waiting = false;
+    if let Some((top, _)) = layers.iter().max_by_key(|(_, layer)| layer.0) {
+        collect_buttons(top, &children, &buttons, &mut order);
+        waiting = graces.get(top).map_or(false, InputGrace::waiting);
+    }
+
+    // Mouse and keyboard share one focus so they don't fight over the highlight
+    for (entity, interaction) in interactions.iter() {
+        if *interaction == Interaction::Hovered && order.contains(&entity) {
+            focus.0 = Some(entity);
+        }
+    }
+    let Some(first) = order.first().copied() else {
+        focus.0 = None;
+        return;
+    };
+    let index = match focus.0.and_then(|f| order.iter().position(|e| *e == f)) {
+        Some(index) => index,
+        None => {
+            focus.0 = Some(first);
+            0
+        }
+    };
+
+    let pad_pressed = |button_type| {
+        gamepads
+            .iter()
+            .any(|gamepad| pad_input.just_pressed(GamepadButton::new(gamepad, button_type)))
+    };
+    let prev = key_input.any_just_pressed([KeyCode::Up, KeyCode::Left, KeyCode::W, KeyCode::A])
+        || pad_pressed(GamepadButtonType::DPadUp)
+        || pad_pressed(GamepadButtonType::DPadLeft);
+    let next = key_input.any_just_pressed([KeyCode::Down, KeyCode::Right, KeyCode::S, KeyCode::D])
+        || pad_pressed(GamepadButtonType::DPadDown)
+        || pad_pressed(GamepadButtonType::DPadRight);
+    let confirm = key_input.any_just_pressed([KeyCode::Return, KeyCode::Space])
+        || pad_pressed(GamepadButtonType::South);
+
+    if prev {
+        focus.0 = Some(order[(index + order.len() - 1) % order.len()]);
+    } else if next {
+        focus.0 = Some(order[(index + 1) % order.len()]);
+    } else if confirm && !waiting {
+        pressed.send(ButtonPressed(order[index]));
+    }
+}
+
+pub fn button_presses(
+    buttons: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
+    graces: Query<&InputGrace>,
+    mut pressed: EventWriter<ButtonPressed>,
+) {
+    if graces.iter().any(InputGrace::waiting) {
+        return;
+    }
+    for (entity, interaction) in buttons.iter() {
+        if *interaction == Interaction::Clicked {
+            pressed.send(ButtonPressed(entity));
+        }
+    }
+}
+
+pub fn button_visuals(
+    mut buttons: Query<(Entity, &Interaction, &mut BackgroundColor), With<Button>>,
+    focus: Res<MenuFocus>,
+) {
+    for (entity, interaction, mut color) in buttons.iter_mut() {
+        *color = match interaction {
+            Interaction::Clicked => PRESSED_BUTTON,
+            _ if focus.0 == Some(entity) => HOVERED_BUTTON,
+            _ => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}